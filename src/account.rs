@@ -1,10 +1,8 @@
-use crate::{Mexc, PROD_API_URL};
-use reqwest::{StatusCode, Response};
-use anyhow::{anyhow, bail};
+use crate::{Mexc, MexcError, error::Result, request::Params};
+use reqwest::{Method, Response};
 use serde::Deserialize;
-use crate::utils::get_timestamp;
 
-use crate::utils::parse_string_to_f64;
+use crate::utils::{Number, parse_string_to_f64, parse_string_to_number};
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct Account {
@@ -23,10 +21,31 @@ pub struct Account {
 #[derive(Deserialize, Debug, Clone)]
 pub struct AccountBalance {
     pub asset: String,
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub free: f64,
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub locked: f64
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub free: Number,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub locked: Number
+}
+
+/// Our own fee rates for a symbol, including VIP level and MX deduction.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TradeFee {
+    #[serde(rename = "makerCommission", deserialize_with = "parse_string_to_f64")]
+    pub maker_commission: f64,
+    #[serde(rename = "takerCommission", deserialize_with = "parse_string_to_f64")]
+    pub taker_commission: f64
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+struct MxDeduct {
+    #[serde(rename = "mxDeductEnable")]
+    mx_deduct_enable: bool
+}
+
+/// `{"data": .., "code": 0, "msg": "success", "timestamp": ..}` wrapper used by the fee endpoints.
+#[derive(Deserialize, Debug)]
+struct DataResponse<T> {
+    data: T
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -37,71 +56,59 @@ pub struct ListenKeyReponse {
 
 impl Mexc {
 
-    pub async fn get_signed(&self, url: &str) -> anyhow::Result<Response> {
-        let api_key = self.api_key.as_ref().ok_or_else(|| anyhow!("Missing api key"))?;
+    pub async fn get_signed(&self, url: &str) -> Result<Response> {
+        let api_key = self.api_key.as_ref().ok_or(MexcError::MissingCredentials("api key"))?;
 
-        let resp = self.client
+        let request = self.client
         .get(url)
-        .header("X-MEXC-APIKEY", api_key)
-        .send().await?;
+        .header("X-MEXC-APIKEY", api_key);
+        let resp = self.send(request).await?;
         Ok(resp)
     }
 
-    pub async fn get_account(&self) -> anyhow::Result<Account> {
-
-        let timestamp = get_timestamp();
-
-        let order_request = format!("timestamp={timestamp}");
-        let signed_order = self.sign_request(order_request)?;
-        let url = format!("{PROD_API_URL}/api/v3/account?{signed_order}");
-        let resp: Response = self.get_signed(&url).await?;
-
-        if resp.status() == StatusCode::OK {
-            let account: Account = resp.json().await?;
-            Ok(account)
-        } else {
-            let err = resp.text().await?;
-            bail!(err);
-        }
+    pub async fn get_account(&self) -> Result<Account> {
+        self.signed_request(Method::GET, "/api/v3/account", Params::new()).await
     }
 
-    pub async fn get_listen_key(&self) -> anyhow::Result<String> {
-
-        let timestamp = get_timestamp();
-        let order_request = format!("timestamp={timestamp}");
-        let signed_order = self.sign_request(order_request)?;
-
-        let url = format!("{PROD_API_URL}/api/v3/userDataStream?{signed_order}");
-        let resp: Response = self.post_signed(&url).await?;
-
-        let keyresp: ListenKeyReponse = resp.json().await?;
+    pub async fn get_listen_key(&self) -> Result<String> {
+        let keyresp: ListenKeyReponse = self.signed_request(Method::POST, "/api/v3/userDataStream", Params::new()).await?;
         Ok(keyresp.listen_key)
     }
 
-    pub async fn keep_alive_listen_key(&self, listen_key: &str) -> anyhow::Result<String> {
+    pub async fn keep_alive_listen_key(&self, listen_key: &str) -> Result<String> {
+        let params = Params::new().add("listenKey", listen_key);
 
-        let timestamp = get_timestamp();
-        let order_request = format!("listenKey={listen_key}&timestamp={timestamp}");
-        let signed_order = self.sign_request(order_request)?;
+        let keyresp: ListenKeyReponse = self.signed_request(Method::PUT, "/api/v3/userDataStream", params).await?;
+        Ok(keyresp.listen_key)
+    }
 
-        let url = format!("{PROD_API_URL}/api/v3/userDataStream?{signed_order}");
-        let resp: Response = self.put_signed(&url).await?;
+    pub async fn delete_listen_key(&self, listen_key: &str) -> Result<String> {
+        let params = Params::new().add("listenKey", listen_key);
 
-        let keyresp: ListenKeyReponse = resp.json().await?;
+        let keyresp: ListenKeyReponse = self.signed_request(Method::DELETE, "/api/v3/userDataStream", params).await?;
         Ok(keyresp.listen_key)
     }
 
-    pub async fn delete_listen_key(&self, listen_key: &str) -> anyhow::Result<String> {
+    /// Maker and taker rates actually charged on `symbol`, unlike the defaults in `SymbolInfo`.
+    pub async fn get_trade_fee(&self, symbol: &str) -> Result<TradeFee> {
+        let params = Params::new().add("symbol", symbol);
 
-        let timestamp = get_timestamp();
-        let order_request = format!("listenKey={listen_key}&timestamp={timestamp}");
-        let signed_order = self.sign_request(order_request)?;
+        let resp: DataResponse<TradeFee> = self.signed_request(Method::GET, "/api/v3/tradeFee", params).await?;
+        Ok(resp.data)
+    }
+
+    /// Whether fees are paid in MX at a discount.
+    pub async fn get_mx_deduct(&self) -> Result<bool> {
+        let resp: DataResponse<MxDeduct> = self.signed_request(Method::GET, "/api/v3/mxDeduct/enable", Params::new()).await?;
+        Ok(resp.data.mx_deduct_enable)
+    }
 
-        let url = format!("{PROD_API_URL}/api/v3/userDataStream?{signed_order}");
-        let resp: Response = self.delete_signed(&url).await?;
+    /// Turns MX fee deduction on or off, returns the new state.
+    pub async fn set_mx_deduct(&self, enable: bool) -> Result<bool> {
+        let params = Params::new().add("mxDeductEnable", enable);
 
-        let keyresp: ListenKeyReponse = resp.json().await?;
-        Ok(keyresp.listen_key)
+        let resp: DataResponse<MxDeduct> = self.signed_request(Method::POST, "/api/v3/mxDeduct/enable", params).await?;
+        Ok(resp.data.mx_deduct_enable)
     }
 }
 
@@ -121,6 +128,29 @@ mod tests {
         dbg!(acc);
     }
 
+    #[test]
+    pub fn test_decode_trade_fee() {
+        let json = r#"{"data":{"makerCommission":0.003000000000000000,"takerCommission":"0.0005"},"code":0,"msg":"success","timestamp":1669109672717}"#;
+        let resp: DataResponse<TradeFee> = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.data, TradeFee { maker_commission: 0.003, taker_commission: 0.0005 });
+
+        let json = r#"{"data":{"mxDeductEnable":true},"code":0,"msg":"success","timestamp":1669109672717}"#;
+        let resp: DataResponse<MxDeduct> = serde_json::from_str(json).unwrap();
+        assert!(resp.data.mx_deduct_enable);
+    }
+
+    #[tokio::test]
+    pub async fn test_get_trade_fee() {
+        let (key, secret) = unlock_keys().unwrap();
+        let client = Mexc::new(Some(key),Some(secret),None).unwrap();
+
+        let fee = client.get_trade_fee("PLSUSDT").await.unwrap();
+        dbg!(fee);
+
+        let mx_deduct = client.get_mx_deduct().await.unwrap();
+        dbg!(mx_deduct);
+    }
+
     #[tokio::test]
     pub async fn test_get_listenkey() {
         let (key, secret) = unlock_keys().unwrap();
@@ -137,7 +167,7 @@ mod tests {
 
         let listen_key = "enter key here";
 
-        let key = client.keep_alive_listen_key(&listen_key).await.unwrap();
+        let key = client.keep_alive_listen_key(listen_key).await.unwrap();
         dbg!(key);
     }
 
@@ -148,7 +178,7 @@ mod tests {
 
         let listen_key = "enter key here";
 
-        let key = client.delete_listen_key(&listen_key).await.unwrap();
+        let key = client.delete_listen_key(listen_key).await.unwrap();
         dbg!(key);
     }
 }
\ No newline at end of file
@@ -0,0 +1,157 @@
+use reqwest::StatusCode;
+use serde::Deserialize;
+use thiserror::Error;
+use crate::error_codes::ErrorCode;
+use crate::validation::OrderValidationError;
+use crate::wallet::WithdrawPolicyError;
+
+pub type Result<T> = std::result::Result<T, MexcError>;
+
+#[derive(Error, Debug)]
+pub enum MexcError {
+    #[error("transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+
+    #[error("http status {status}: {body}")]
+    HttpStatus {
+        status: StatusCode,
+        body: String
+    },
+
+    /// Spot error body `{"code": .., "msg": ..}`
+    #[error("mexc api error {code}: {msg}")]
+    Api {
+        status: StatusCode,
+        code: i64,
+        msg: String
+    },
+
+    /// Futures response with `success == false`
+    #[error("mexc futures api error {code}: {message:?}")]
+    FuturesApi {
+        code: i64,
+        message: Option<String>,
+        body: String
+    },
+
+    #[error("failed to decode response: {source} on {body}")]
+    Decode {
+        source: serde_json::Error,
+        body: String
+    },
+
+    #[error("missing credentials: {0}")]
+    MissingCredentials(&'static str),
+
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("order rejected before submission: {0}")]
+    InvalidOrder(#[from] OrderValidationError),
+
+    #[error("withdrawal rejected by policy: {0}")]
+    WithdrawRejected(#[from] WithdrawPolicyError),
+
+    #[error("invalid header value: {0}")]
+    InvalidHeader(#[from] reqwest::header::InvalidHeaderValue)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ApiErrorBody {
+    pub code: i64,
+    pub msg: String
+}
+
+impl MexcError {
+
+    /// Builds the error for a non-success spot response, decoding `{code, msg}` when present.
+    pub fn from_spot_response(status: StatusCode, body: String) -> Self {
+        match serde_json::from_str::<ApiErrorBody>(&body) {
+            Ok(err) => MexcError::Api { status, code: err.code, msg: err.msg },
+            Err(_) => MexcError::HttpStatus { status, body }
+        }
+    }
+
+    /// The MEXC error code carried by `Api` and `FuturesApi` errors.
+    pub fn code(&self) -> Option<i64> {
+        match self {
+            MexcError::Api { code, .. } => Some(*code),
+            MexcError::FuturesApi { code, .. } => Some(*code),
+            _ => None
+        }
+    }
+
+    /// Classifies the MEXC error code, see `ErrorCode`.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            MexcError::Api { code, .. } => Some(ErrorCode::from_spot(*code)),
+            MexcError::FuturesApi { code, .. } => Some(ErrorCode::from_futures(*code)),
+            _ => None
+        }
+    }
+
+    fn status(&self) -> Option<StatusCode> {
+        match self {
+            MexcError::HttpStatus { status, .. } => Some(*status),
+            MexcError::Api { status, .. } => Some(*status),
+            MexcError::Transport(err) => err.status(),
+            _ => None
+        }
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, MexcError::Transport(err) if err.is_timeout())
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS) ||
+        self.error_code().is_some_and(|code| code.is_rate_limited())
+    }
+
+    /// The request was stamped outside its recvWindow, the clients resync before the next one.
+    pub fn is_recv_window_error(&self) -> bool {
+        self.error_code() == Some(ErrorCode::TimestampOutsideRecvWindow)
+    }
+
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self, MexcError::MissingCredentials(_)) ||
+        self.error_code().is_some_and(|code| code.is_auth_error())
+    }
+
+    /// Timeouts, dropped connections, 429 and 5xx responses, and retryable MEXC codes.
+    pub fn is_retryable(&self) -> bool {
+        if let MexcError::Transport(err) = self {
+            if err.is_timeout() || err.is_connect() || err.is_request() {
+                return true;
+            }
+        }
+
+        if let Some(status) = self.status() {
+            if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                return true;
+            }
+        }
+
+        self.error_code().is_some_and(|code| code.is_retryable())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_decode_spot_error_body() {
+        let body = r#"{"code":700002,"msg":"Signature for this request is not valid."}"#.to_string();
+        let err = MexcError::from_spot_response(StatusCode::BAD_REQUEST, body);
+        assert_eq!(err.code(), Some(700002));
+
+        let err = MexcError::from_spot_response(StatusCode::BAD_GATEWAY, "<html></html>".into());
+        assert!(matches!(err, MexcError::HttpStatus { .. }));
+        assert!(err.is_retryable());
+
+        let body = r#"{"code":700003,"msg":"Timestamp for this request is outside of the recvWindow."}"#.to_string();
+        assert!(MexcError::from_spot_response(StatusCode::BAD_REQUEST, body).is_recv_window_error());
+    }
+}
@@ -0,0 +1,129 @@
+// https://mexcdevelop.github.io/apidocs/spot_v3_en/#error-code
+// https://mexcdevelop.github.io/apidocs/contract_v1_en/#error-code-example
+
+/// Known MEXC error codes grouped by what the caller should do about them.
+/// Spot and futures use separate code spaces, see `from_spot` and `from_futures`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    SignatureInvalid,
+    InvalidApiKey,
+    PermissionDenied,
+    IpNotWhitelisted,
+    TimestampOutsideRecvWindow,
+    TooManyRequests,
+    InternalError,
+    ServiceUnavailable,
+    RepeatedRequest,
+    InsufficientBalance,
+    InsufficientPosition,
+    Oversold,
+    UnknownOrder,
+    InvalidSymbol,
+    TradingDisabled,
+    QuantityTooSmall,
+    QuantityTooLarge,
+    PrecisionError,
+    InvalidParameter,
+    OrderLimitExceeded,
+    RiskControl,
+    AccountAbnormal,
+    Unknown(i64)
+}
+
+impl ErrorCode {
+
+    pub fn from_spot(code: i64) -> Self {
+        match code {
+            602 | 700002 => ErrorCode::SignatureInvalid,
+            400 | 10072 | 700001 => ErrorCode::InvalidApiKey,
+            401 | 403 | 700007 => ErrorCode::PermissionDenied,
+            700006 => ErrorCode::IpNotWhitelisted,
+            10073 | 700003 => ErrorCode::TimestampOutsideRecvWindow,
+            429 => ErrorCode::TooManyRequests,
+            500 => ErrorCode::InternalError,
+            503 | 504 => ErrorCode::ServiceUnavailable,
+            10101 => ErrorCode::InsufficientBalance,
+            30004 => ErrorCode::InsufficientPosition,
+            30005 => ErrorCode::Oversold,
+            -2011 | -2013 => ErrorCode::UnknownOrder,
+            30014 | 30021 | 730001 => ErrorCode::InvalidSymbol,
+            30001 | 30016 | 30018 | 30019 | 30020 | 30041 => ErrorCode::TradingDisabled,
+            30002 => ErrorCode::QuantityTooSmall,
+            30003 => ErrorCode::QuantityTooLarge,
+            700004 | 700005 | 700008 | 730002 => ErrorCode::InvalidParameter,
+            30027 | 30029 | 30032 => ErrorCode::OrderLimitExceeded,
+            30028 => ErrorCode::RiskControl,
+            10001 | 60005 | 730100 => ErrorCode::AccountAbnormal,
+            code => ErrorCode::Unknown(code)
+        }
+    }
+
+    pub fn from_futures(code: i64) -> Self {
+        match code {
+            602 => ErrorCode::SignatureInvalid,
+            401 | 402 => ErrorCode::InvalidApiKey,
+            511 | 701 | 702 | 703 | 704 => ErrorCode::PermissionDenied,
+            406 => ErrorCode::IpNotWhitelisted,
+            513 => ErrorCode::TimestampOutsideRecvWindow,
+            510 => ErrorCode::TooManyRequests,
+            500 => ErrorCode::InternalError,
+            501 | 9999 => ErrorCode::ServiceUnavailable,
+            603 => ErrorCode::RepeatedRequest,
+            2005 | 2018 => ErrorCode::InsufficientBalance,
+            2009 => ErrorCode::InsufficientPosition,
+            1001 => ErrorCode::InvalidSymbol,
+            1002 => ErrorCode::TradingDisabled,
+            2008 => ErrorCode::QuantityTooSmall,
+            2011 | 2016 | 2029 | 2030 | 2031 => ErrorCode::QuantityTooLarge,
+            2015 => ErrorCode::PrecisionError,
+            506 | 600 | 601 | 1004 | 2001 | 2002 | 2003 | 2004 | 2006 | 2007 | 2021 | 2022 => ErrorCode::InvalidParameter,
+            2013 | 2014 | 2023 | 2024 | 2025 => ErrorCode::OrderLimitExceeded,
+            1003 => ErrorCode::RiskControl,
+            1000 => ErrorCode::AccountAbnormal,
+            code => ErrorCode::Unknown(code)
+        }
+    }
+
+    /// Transient failures where resending the same request may succeed.
+    /// A recvWindow rejection invalidates the client's clock offset, so a resend is stamped after a resync.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self,
+            ErrorCode::TooManyRequests |
+            ErrorCode::InternalError |
+            ErrorCode::ServiceUnavailable |
+            ErrorCode::TimestampOutsideRecvWindow
+        )
+    }
+
+    /// Credential or permission problems, retrying will not help.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self,
+            ErrorCode::SignatureInvalid |
+            ErrorCode::InvalidApiKey |
+            ErrorCode::PermissionDenied |
+            ErrorCode::IpNotWhitelisted
+        )
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, ErrorCode::TooManyRequests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_classify_codes() {
+        assert!(ErrorCode::from_spot(700002).is_auth_error());
+        assert!(ErrorCode::from_futures(602).is_auth_error());
+        assert_eq!(ErrorCode::from_spot(30004), ErrorCode::InsufficientPosition);
+        assert_eq!(ErrorCode::from_futures(2005), ErrorCode::InsufficientBalance);
+        assert!(ErrorCode::from_spot(429).is_rate_limited());
+        assert!(ErrorCode::from_futures(510).is_retryable());
+        assert!(!ErrorCode::from_futures(2005).is_retryable());
+        assert_eq!(ErrorCode::from_spot(123456), ErrorCode::Unknown(123456));
+    }
+}
@@ -1,42 +1,112 @@
 pub mod structures;
+pub mod registry;
 
-use serde_json::json;
-use anyhow::Context;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde_json::{json, Value};
 use hmac::{Hmac, Mac};
 use md5::{Digest, Md5};
 use reqwest::Response;
 use sha2::Sha256;
-use reqwest::Client;
+use reqwest::{Client, Method, Request, RequestBuilder};
+use futures::future::BoxFuture;
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
-use anyhow::{anyhow, bail};
 use reqwest::header::{HeaderMap, HeaderValue};
-use crate::utils::get_timestamp;
+use crate::error::{MexcError, Result};
+use crate::utils::{Number, format_number, get_timestamp, trim_base_url};
+use crate::time_sync::{ClockOffset, TimeSync, DEFAULT_TIME_SYNC_INTERVAL};
+use crate::rate_limit::{RateLimiter, RateLimitStats, TokenBucketLimiter, futures_request_weights};
+use crate::retry::{self, RetryPolicy};
+use crate::request::Params;
 
 
 use structures::*;
+pub use registry::ContractRegistry;
 
 pub const FUTURES_API_URL: &str = "https://contract.mexc.com";
+pub const FUTURES_WEB_URL: &str = "https://futures.mexc.com";
 
 pub struct MexcFutures {
     pub api_key: Option<String>,
     pub api_secret: Option<String>,
     pub web_user_token: Option<String>,
+    pub base_url: String,
+    pub web_base_url: String,
+    pub time_sync: TimeSync,
+    pub rate_limiter: Arc<dyn RateLimiter>,
+    pub retry_policy: RetryPolicy,
     pub client: Client
 }
 
-
-fn get_md5(string: &str) -> String {
-    let mut hasher = Md5::new();
-    hasher.update(string);
-    format!("{:x}", hasher.finalize())
+#[derive(Default, Clone)]
+pub struct MexcFuturesBuilder {
+    api_key: Option<String>,
+    api_secret: Option<String>,
+    web_user_token: Option<String>,
+    proxy_url: Option<String>,
+    base_url: Option<String>,
+    web_base_url: Option<String>,
+    time_sync_interval: Option<Duration>,
+    rate_limiter: Option<Arc<dyn RateLimiter>>,
+    retry_policy: Option<RetryPolicy>
 }
 
-impl MexcFutures {
+impl MexcFuturesBuilder {
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn api_secret(mut self, api_secret: impl Into<String>) -> Self {
+        self.api_secret = Some(api_secret.into());
+        self
+    }
+
+    pub fn web_user_token(mut self, web_user_token: impl Into<String>) -> Self {
+        self.web_user_token = Some(web_user_token.into());
+        self
+    }
+
+    pub fn proxy_url(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy_url = Some(proxy_url.into());
+        self
+    }
 
-    pub fn new(api_key: Option<String>, api_secret: Option<String>, web_user_token: Option<String>, proxy_url: Option<String>) -> anyhow::Result<Self> {
+    /// Futures REST host, defaults to `FUTURES_API_URL`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Host used for web token order placement, defaults to `FUTURES_WEB_URL`.
+    pub fn web_base_url(mut self, web_base_url: impl Into<String>) -> Self {
+        self.web_base_url = Some(web_base_url.into());
+        self
+    }
 
-        let client = match proxy_url {
+    /// Resync the clock offset against `get_server_time` when older than `interval`, defaults to `DEFAULT_TIME_SYNC_INTERVAL`.
+    pub fn time_sync_interval(mut self, interval: Duration) -> Self {
+        self.time_sync_interval = Some(interval);
+        self
+    }
+
+    /// Replaces the default `TokenBucketLimiter`.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<dyn RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Defaults to `RetryPolicy::default()`. Order creation is a POST and never retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    pub fn build(self) -> Result<MexcFutures> {
+
+        let client = match self.proxy_url {
             Some(url) => {
                 let proxy = reqwest::Proxy::all(url)?;
                 reqwest::Client::builder().proxy(proxy).build()?
@@ -44,20 +114,74 @@ impl MexcFutures {
             None => reqwest::Client::new()
         };
 
+        let base_url = trim_base_url(self.base_url.as_deref().unwrap_or(FUTURES_API_URL));
+        let web_base_url = trim_base_url(self.web_base_url.as_deref().unwrap_or(FUTURES_WEB_URL));
+
+        Ok(MexcFutures {
+            api_key: self.api_key,
+            api_secret: self.api_secret,
+            web_user_token: self.web_user_token,
+            base_url,
+            web_base_url,
+            time_sync: TimeSync::new(Some(self.time_sync_interval.unwrap_or(DEFAULT_TIME_SYNC_INTERVAL))),
+            rate_limiter: self.rate_limiter.unwrap_or_else(|| Arc::new(TokenBucketLimiter::default())),
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            client
+        })
+    }
+}
 
-        Ok(Self {
+
+fn get_md5(string: &str) -> String {
+    let mut hasher = Md5::new();
+    hasher.update(string);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Decodes the `FuturesResponse<T>` envelope, turning `success == false` into `MexcError::FuturesApi`.
+/// The raw body is kept in every error.
+async fn decode_futures_response<T: DeserializeOwned>(resp: Response) -> Result<T> {
+    let status = resp.status();
+    let body = resp.text().await?;
+
+    match serde_json::from_str::<FuturesResponse<T>>(&body) {
+        Ok(envelope) if envelope.success => match envelope.data {
+            Some(data) => Ok(data),
+            None => serde_json::from_value(Value::Null).map_err(|source| MexcError::Decode { source, body })
+        },
+        Ok(envelope) => Err(MexcError::FuturesApi { code: envelope.code, message: envelope.message, body }),
+        Err(source) => {
+            // Error responses usually carry a data payload of a different shape than T
+            match serde_json::from_str::<FuturesResponse<IgnoredAny>>(&body) {
+                Ok(envelope) if !envelope.success => Err(MexcError::FuturesApi { code: envelope.code, message: envelope.message, body }),
+                _ if !status.is_success() => Err(MexcError::HttpStatus { status, body }),
+                _ => Err(MexcError::Decode { source, body })
+            }
+        }
+    }
+}
+
+impl MexcFutures {
+
+    pub fn new(api_key: Option<String>, api_secret: Option<String>, web_user_token: Option<String>, proxy_url: Option<String>) -> Result<Self> {
+        MexcFuturesBuilder {
             api_key,
             api_secret,
             web_user_token,
-            client
-        })
+            proxy_url,
+            ..Default::default()
+        }.build()
+    }
+
+    pub fn builder() -> MexcFuturesBuilder {
+        MexcFuturesBuilder::default()
     }
 
-    pub fn sign_v1(&self, timestamp: u128, sign_params: Option<&str>) -> anyhow::Result<String> {
+    pub fn sign_v1(&self, timestamp: u128, sign_params: Option<&str>) -> Result<String> {
 
 
-        let api_key = self.api_key.as_ref().ok_or_else(|| anyhow!("Missing api key"))?;
-        let secret_key = self.api_secret.as_ref().ok_or_else(|| anyhow!("Missing secret key"))?;
+        let api_key = self.api_key.as_ref().ok_or(MexcError::MissingCredentials("api key"))?;
+        let secret_key = self.api_secret.as_ref().ok_or(MexcError::MissingCredentials("secret key"))?;
 
 
         let sign = match sign_params {
@@ -72,44 +196,145 @@ impl MexcFutures {
         Ok(hex::encode(result.into_bytes()))
     }
 
-    pub async fn ping(&self) -> anyhow::Result<Duration> {
-        let url = format!("{FUTURES_API_URL}/api/v1/contract/ping");
+    /// Shared request path, every futures request waits on the per-path rate limit and is retried per `retry_policy`.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let request = request.build()?;
+        let path = request.url().path().to_string();
 
-        let inst = Instant::now();
-        let _ = self.client.get(url).send().await?;
+        self.send_with(&path, || {
+            let request = request.try_clone().ok_or_else(|| MexcError::InvalidRequest("request body can't be resent".to_string()));
+            Box::pin(std::future::ready(request))
+        }).await
+    }
 
-        Ok(inst.elapsed())
+    /// Like `send` but the request is built by `build` once the rate limiter lets it through,
+    /// so signed headers carry a fresh `Request-Time`.
+    async fn send_with<'a, F>(&self, path: &str, build: F) -> Result<Response>
+    where
+        F: FnMut() -> BoxFuture<'a, Result<Request>>,
+    {
+        let weights = futures_request_weights(path);
+        retry::execute(&self.client, self.rate_limiter.as_ref(), &self.retry_policy, &weights, build).await
     }
 
-    pub async fn get_futures_account(&self) -> anyhow::Result<Vec<FuturesBalance>> {
+    /// Sends the request and decodes the `FuturesResponse<T>` envelope.
+    async fn execute<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let resp = self.send(request).await?;
+        decode_futures_response(resp).await
+    }
 
-        let url = format!("{}/api/v1/private/account/assets", FUTURES_API_URL);
+    /// Decodes a signed response, invalidating the clock offset when the timestamp was rejected.
+    async fn decode_signed<T: DeserializeOwned>(&self, resp: Response) -> Result<T> {
+        decode_futures_response(resp).await.inspect_err(|err| {
+            if err.is_recv_window_error() {
+                self.time_sync.invalidate();
+            }
+        })
+    }
 
-        let headers = self.generate_signed_header(None)?;
+    /// Futures queries are sorted by key and encode spaces as `%20`, which is also the string signed for GET and DELETE.
+    fn encode_query(params: Params) -> String {
+        params.sorted().encode().replace('+', "%20")
+    }
 
-        let resp: Response = self.client.get(url).headers(headers).send().await?;
+    fn url(&self, path: &str, query: &str) -> String {
+        if query.is_empty() {
+            format!("{}{path}", self.base_url)
+        } else {
+            format!("{}{path}?{query}", self.base_url)
+        }
+    }
 
-        let json_str: String = resp.text().await?;
+    async fn public_request<T: DeserializeOwned>(&self, path: &str, params: Params) -> Result<T> {
+        let url = self.url(path, &Self::encode_query(params));
+        self.execute(self.client.get(url)).await
+    }
 
-        //println!("{json_str}");
+    /// Signs with `sign_v1` over the json body when present, otherwise over the sorted query string.
+    async fn signed_request<T: DeserializeOwned>(&self, method: Method, path: &str, params: Params, body: Option<Value>) -> Result<T> {
+        let query = Self::encode_query(params);
+        let url = self.url(path, &query);
 
-        let resp: FuturesResponse = serde_json::from_str(&json_str)?;
+        let body = body.map(|body| body.to_string());
 
-        //dbg!(&resp);
+        let sign_params = match &body {
+            Some(body) => Some(body.as_str()),
+            None if !query.is_empty() => Some(query.as_str()),
+            None => None
+        };
 
-        if !resp.success {
-            bail!("mexc futures err resp: {:?}", resp.message);
-        }
+        let resp = self.send_with(path, || {
+            let method = method.clone();
+            let (url, body, sign_params) = (&url, &body, &sign_params);
+
+            Box::pin(async move {
+                let headers = self.generate_signed_header(*sign_params).await?;
+
+                let mut request = self.client.request(method, url).headers(headers);
+
+                // Send the exact string that was signed
+                if let Some(body) = body {
+                    request = request.body(body.clone());
+                }
+
+                Ok(request.build()?)
+            })
+        }).await?;
+
+        self.decode_signed(resp).await
+    }
+
+    pub fn rate_limit_stats(&self) -> RateLimitStats {
+        self.rate_limiter.stats()
+    }
 
-        let balances: Vec<FuturesBalance> = serde_json::from_value(resp.data.context("Expected data field")?)?;
+    pub async fn ping(&self) -> Result<Duration> {
+        let url = format!("{}/api/v1/contract/ping", self.base_url);
 
-        Ok(balances)
+        let inst = Instant::now();
+        let _ = self.send(self.client.get(url)).await?;
 
+        Ok(inst.elapsed())
     }
 
-    fn generate_signed_header(&self, sign_params: Option<&str>) -> anyhow::Result<HeaderMap> {
-        let api_key = self.api_key.as_ref().ok_or_else(|| anyhow!("Missing api key"))?;
-        let timestamp = get_timestamp();
+    pub async fn get_server_time(&self) -> Result<u128> {
+        self.public_request("/api/v1/contract/ping", Params::new()).await
+    }
+
+    /// Measures the clock offset against the server and applies it to subsequent signed requests.
+    /// The probe is charged to the rate limiter but bypasses retries, and only the http exchange is timed,
+    /// since a limiter wait or backoff inside the round trip would skew the midpoint.
+    pub async fn sync_time(&self) -> Result<ClockOffset> {
+        let path = "/api/v1/contract/ping";
+        self.rate_limiter.acquire(&futures_request_weights(path)).await;
+
+        let sent_at = get_timestamp();
+        let inst = Instant::now();
+        let resp = self.client.get(self.url(path, "")).send().await?;
+        let round_trip = inst.elapsed();
+
+        let server_time: u128 = decode_futures_response(resp).await?;
+        Ok(self.time_sync.record(sent_at, round_trip, server_time))
+    }
+
+    pub fn clock_offset(&self) -> ClockOffset {
+        self.time_sync.offset()
+    }
+
+    /// Corrected timestamp for signed requests, resyncing first when the offset is stale.
+    /// Falls back to the last offset when the resync fails.
+    pub async fn timestamp(&self) -> Result<u128> {
+        self.time_sync.sync_if_needed(|| self.sync_time()).await?;
+        Ok(self.time_sync.now())
+    }
+
+    pub async fn get_futures_account(&self) -> Result<Vec<FuturesBalance>> {
+        self.signed_request(Method::GET, "/api/v1/private/account/assets", Params::new(), None).await
+    }
+
+    async fn generate_signed_header(&self, sign_params: Option<&str>) -> Result<HeaderMap> {
+        let api_key = self.api_key.as_ref().ok_or(MexcError::MissingCredentials("api key"))?;
+        let timestamp = self.timestamp().await?;
         let signature = self.sign_v1(timestamp, sign_params)?;
         let request_time = timestamp.to_string();
 
@@ -121,30 +346,9 @@ impl MexcFutures {
         Ok(headers)
     }
     
-    pub async fn get_account_asset(&self, asset: &str) -> anyhow::Result<FuturesBalance> {
-
+    pub async fn get_account_asset(&self, asset: &str) -> Result<FuturesBalance> {
         let path = format!("/api/v1/private/account/asset/{}", asset);
-        let url = format!("{}{}", FUTURES_API_URL, path);
-
-        let headers = self.generate_signed_header(None)?;
-
-        let resp: Response = self.client.get(url).headers(headers).send().await?;
-
-        let json_str: String = resp.text().await?;
-
-        //println!("{json_str}");
-
-        let resp: FuturesResponse = serde_json::from_str(&json_str)?;
-
-        //dbg!(&resp);
-
-        if !resp.success {
-            bail!("mexc futures err resp: {:?}", resp.message);
-        }
-
-        let balance: FuturesBalance = serde_json::from_value(resp.data.context("Expected data field")?)?;
-
-        Ok(balance)
+        self.signed_request(Method::GET, &path, Params::new(), None).await
     }
 
     /*
@@ -152,12 +356,13 @@ impl MexcFutures {
     Use field userToken as web user token from: https://www.mexc.com/ucenter/api/user_info
     
      */
-    pub async fn submit_order(&self, symbol: &str, contract_units: u64, price: Option<f64>,leverage: u64, side: OrderDirection, open_type: OpenType, order_type: OrderType) -> anyhow::Result<OrderReceipt> {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn submit_order(&self, symbol: &str, contract_units: u64, price: Option<Number>,leverage: u64, side: OrderDirection, open_type: OpenType, order_type: OrderType) -> Result<OrderReceipt> {
 
 
-        let web_user_token = self.web_user_token.as_ref().ok_or_else(|| anyhow!("Missing web user token"))?;
+        let web_user_token = self.web_user_token.as_ref().ok_or(MexcError::MissingCredentials("web user token"))?;
 
-        let url = "https://futures.mexc.com/api/v1/private/order/create";
+        let url = format!("{}/api/v1/private/order/create", self.web_base_url);
 
         let mut params = json!({
             "symbol": symbol,
@@ -172,56 +377,49 @@ impl MexcFutures {
         });
 
         if let Some(p) = price {
-            params["price"] = json!(p.to_string());
+            params["price"] = json!(format_number(p));
         }
 
 
-        let timestamp = get_timestamp().to_string();
-
-        let partial_hash =  {
-            let concat = format!("{web_user_token}{timestamp}");
-            //println!("to hash: {concat}");
-            get_md5(&concat).get(7..).unwrap().to_string()
-        };
-        //println!("partial_hash: {partial_hash}");
-    
         let param_string = params.to_string();
         //println!("param_string: {param_string}");
-    
-        let signature = get_md5(&format!("{timestamp}{param_string}{partial_hash}"));
-        //println!("signature: {signature}");
-    
-        let mut headers = HeaderMap::new();
-            
-        headers.insert("x-mxc-nonce", HeaderValue::from_str(&timestamp)?);
-        headers.insert("x-mxc-sign", HeaderValue::from_str(&signature)?);
-        headers.insert("authorization", HeaderValue::from_str(web_user_token)?);
-        headers.insert("user-agent", HeaderValue::from_static("MEXC/7 CFNetwork/1474 Darwin/23.0.0"));
-        headers.insert("content-type", HeaderValue::from_static("application/json"));
-        headers.insert("origin", HeaderValue::from_static("https://futures.mexc.com"));
-        headers.insert("referer", HeaderValue::from_static("https://futures.mexc.com/exchange"));
-
-        let txt = self.client.post(url).headers(headers).json(&params).send().await?.text().await?;
-
-        let result: Result<FuturesResponse,serde_json::Error> = serde_json::from_str(&txt);
-
-        let resp = match result {
-            Ok(resp) => resp,
-            Err(_) => {
-                bail!("Bad web token");
-            }
-        };
 
-        if !resp.success {
-            bail!("mexc futures err resp: {:?}", resp.message);
-        }
+        let resp = self.send_with("/api/v1/private/order/create", || {
+            let (url, param_string) = (&url, &param_string);
+
+            Box::pin(async move {
+                let timestamp = self.timestamp().await?.to_string();
+
+                let partial_hash =  {
+                    let concat = format!("{web_user_token}{timestamp}");
+                    //println!("to hash: {concat}");
+                    get_md5(&concat).get(7..).unwrap().to_string()
+                };
+                //println!("partial_hash: {partial_hash}");
+
+                let signature = get_md5(&format!("{timestamp}{param_string}{partial_hash}"));
+                //println!("signature: {signature}");
+
+                let mut headers = HeaderMap::new();
+
+                headers.insert("x-mxc-nonce", HeaderValue::from_str(&timestamp)?);
+                headers.insert("x-mxc-sign", HeaderValue::from_str(&signature)?);
+                headers.insert("authorization", HeaderValue::from_str(web_user_token)?);
+                headers.insert("user-agent", HeaderValue::from_static("MEXC/7 CFNetwork/1474 Darwin/23.0.0"));
+                headers.insert("content-type", HeaderValue::from_static("application/json"));
+                headers.insert("origin", HeaderValue::from_str(&self.web_base_url)?);
+                headers.insert("referer", HeaderValue::from_str(&format!("{}/exchange", self.web_base_url))?);
 
-        let receipt: OrderReceipt = serde_json::from_value(resp.data.context("Expected data field")?)?;
+                Ok(self.client.post(url).headers(headers).body(param_string.clone()).build()?)
+            })
+        }).await?;
 
-        Ok(receipt)
+        // A bad web token comes back as a non-json body, kept in MexcError::Decode
+        self.decode_signed(resp).await
     }
 
-    pub async fn submit_directional_orders(&self, symbol: &str, mut contract_units: u64, price: Option<f64>,leverage: u64, direction: PositionType, open_type: OpenType, order_type: OrderType) -> anyhow::Result<Vec<OrderReceipt>> {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn submit_directional_orders(&self, symbol: &str, mut contract_units: u64, price: Option<Number>,leverage: u64, direction: PositionType, open_type: OpenType, order_type: OrderType) -> Result<Vec<OrderReceipt>> {
 
         let open_positions = self.get_open_positions().await?;
 
@@ -259,136 +457,48 @@ impl MexcFutures {
         Ok(orders)
     }
 
-    pub async fn get_open_positions(&self) -> anyhow::Result<Vec<FuturesPosition>> {
-
-        let url = format!("{}/api/v1/private/position/open_positions", FUTURES_API_URL);
-
-        let headers = self.generate_signed_header(None)?;
-
-        let resp: Response = self.client.get(url).headers(headers).send().await?;
-
-        let json_str: String = resp.text().await?;
-
-        //println!("{json_str}");
-
-        let resp: FuturesResponse = serde_json::from_str(&json_str)?;
-
-        //dbg!(&resp);
-
-        if !resp.success {
-            bail!("mexc futures err resp: {:?}", resp.message);
-        }
-
-        let positions: Vec<FuturesPosition> = serde_json::from_value(resp.data.context("Expected data field")?)?;
-        
-
-        Ok(positions)
+    pub async fn get_open_positions(&self) -> Result<Vec<FuturesPosition>> {
+        self.signed_request(Method::GET, "/api/v1/private/position/open_positions", Params::new(), None).await
     }
 
 
-    pub async fn get_fair_price(&self, symbol: &str) -> anyhow::Result<f64> {
-        let url = format!("{}/api/v1/contract/index_price/{}", FUTURES_API_URL, symbol);
-        let resp: FuturesResponse = self.client.get(url).send().await?.json().await?;
+    pub async fn get_fair_price(&self, symbol: &str) -> Result<Number> {
+        let path = format!("/api/v1/contract/index_price/{}", symbol);
 
-        if !resp.success {
-            bail!("mexc futures err resp: {:?}", resp.message);
-        }
-        resp.data.context("Expected data field")?.get("indexPrice").context("Expected index price")?.as_f64().context("f64 convert error")
+        let index_price: IndexPrice = self.public_request(&path, Params::new()).await?;
+        Ok(index_price.index_price)
     }
 
-    pub async fn get_contract_details(&self, symbol: &str) -> anyhow::Result<ContractInfo> {
-
-        let url = format!("{}/api/v1/contract/detail?symbol={}", FUTURES_API_URL,symbol);
-
-        let resp: FuturesResponse = self.client.get(url).send().await?.json().await?;
-
-        if !resp.success {
-            bail!("mexc futures err resp: {:?}", resp.message);
-        }
-        
-        let detail: ContractInfo = serde_json::from_value(resp.data.context("Expected data field")?)?;
-
-        Ok(detail)
+    pub async fn get_contract_details(&self, symbol: &str) -> Result<ContractInfo> {
+        let params = Params::new().add("symbol", symbol);
+        self.public_request("/api/v1/contract/detail", params).await
     }
 
-    pub async fn query_order(&self, order_id: &str) -> anyhow::Result<FuturesOrder> {
-
-        let url = format!("{}/api/v1/private/order/get/{order_id}", FUTURES_API_URL);
-
-        let headers = self.generate_signed_header(None)?;
-
-        let resp: Response = self.client.get(url).headers(headers).send().await?;
-
-        let json_str: String = resp.text().await?;
-
-        let resp: FuturesResponse = serde_json::from_str(&json_str)?;
-
-        if !resp.success {
-            bail!("mexc futures err resp: {:?}", resp.message);
-        }
-
-        let order: FuturesOrder = serde_json::from_value(resp.data.context("Expected data field")?)?;
-    
-        Ok(order)
+    pub async fn query_order(&self, order_id: &str) -> Result<FuturesOrder> {
+        let path = format!("/api/v1/private/order/get/{order_id}");
+        self.signed_request(Method::GET, &path, Params::new(), None).await
     }
-    /* 
-    // Does not work... signature verification failed
-    pub async fn query_orders(&self, order_ids: Vec<String>) -> anyhow::Result<Vec<FuturesOrder>> {
-
-        ensure!(order_ids.len() > 0, "No orders");
-
-        let url = format!("{}/api/v1/private/order/batch_query", FUTURES_API_URL);
-
-        let params = json!({
-            "order_ids": order_ids.join(",")
-        });
-
-        let headers = self.generate_signed_header(Some(&params.to_string()))?;
-
-        let resp: Response = self.client.get(url).headers(headers).json(&params).send().await?;
-
-        let json_str: String = resp.text().await?;
-
-        let resp: FuturesResponse = serde_json::from_str(&json_str)?;
-
-        if !resp.success {
-            bail!("mexc futures err resp: {:?}", resp.message);
-        }
-
-        let orders: Vec<FuturesOrder> = serde_json::from_value(resp.data.context("Expected data field")?)?;
-    
-        Ok(orders)
-    }
-    */
-
-    /* 
-    pub async fn get_all_contract_details(&self) -> anyhow::Result<()> {
-
-        let url = format!("{}/api/v1/contract/detail", FUTURES_API_URL);
+    /// Looks up to 50 orders in one request.
+    pub async fn query_orders(&self, order_ids: &[&str]) -> Result<Vec<FuturesOrder>> {
 
-        let resp: FuturesResponse = self.client.get(url).send().await?.json().await?;
-
-        if !resp.success {
-            bail!("mexc futures err resp: {:?}", resp.message);
+        if order_ids.is_empty() || order_ids.len() > 50 {
+            return Err(MexcError::InvalidRequest("Expected between 1 and 50 order ids".into()));
         }
 
-        //let txt = resp.data.to_string();
-       // println!("{txt}");
-        
-        let details: Vec<ContractInfo> = serde_json::from_value(resp.data.context("Expected data field")?)?;
-
-
-        dbg!(&details);
+        let params = Params::new().add("order_ids", order_ids.join(","));
 
-        Ok(())
+        self.signed_request(Method::GET, "/api/v1/private/order/batch_query", params, None).await
     }
 
-    */
+    pub async fn get_all_contract_details(&self) -> Result<Vec<ContractInfo>> {
+        let contracts: ContractList = self.public_request("/api/v1/contract/detail", Params::new()).await?;
+        Ok(contracts.0)
+    }
 
-    pub fn create_websocket_login_statement(&self) -> anyhow::Result<String> {
+    pub fn create_websocket_login_statement(&self) -> Result<String> {
 
-        let api_key = self.api_key.as_ref().ok_or_else(|| anyhow!("Missing api key"))?;
-        let timestamp = get_timestamp();
+        let api_key = self.api_key.as_ref().ok_or(MexcError::MissingCredentials("api key"))?;
+        let timestamp = self.time_sync.now();
         let signature = self.sign_v1(timestamp, None)?;
 
         let cmd = json!({
@@ -427,6 +537,15 @@ mod tests {
         dbg!(dur);
     }
 
+    #[tokio::test]
+    pub async fn test_futures_sync_time() {
+
+        let client = MexcFutures::new(None,None,None,None).unwrap();
+
+        let offset = client.sync_time().await.unwrap();
+        dbg!(offset);
+    }
+
     #[tokio::test]
     pub async fn test_get_futures_account() {
         let (key, secret) = unlock_keys().unwrap();
@@ -470,16 +589,24 @@ mod tests {
         dbg!(p);
     }
 
-    /* 
     #[tokio::test]
     pub async fn test_futures_get_all_contract_details() {
 
         let client = MexcFutures::new(None,None,None, None).unwrap();
         let p = client.get_all_contract_details().await.unwrap();
-        dbg!(p);
+        dbg!(p.len());
     }
 
-    */
+    #[tokio::test]
+    pub async fn test_futures_contract_registry() {
+
+        let client = MexcFutures::new(None,None,None, None).unwrap();
+        let registry = ContractRegistry::load(&client, Duration::from_secs(300)).await.unwrap();
+
+        dbg!(registry.get("ETH_USDT").map(|c| c.contract_size));
+        dbg!(registry.by_base_coin("BTC").len());
+        dbg!(registry.by_settle_coin("USDT").len());
+    }
 
     #[tokio::test]
     pub async fn test_futures_submit_order() {
@@ -546,7 +673,6 @@ mod tests {
         // taker_fee: 0.0049071
     }
 
-    /*
     #[tokio::test]
     pub async fn test_futures_query_multiple_order() {
         let (key, secret) = unlock_keys().unwrap();
@@ -555,13 +681,23 @@ mod tests {
         // market order 575758889245571072
         // limit order 575755030422977024
 
-        let orders = vec!["575758889245571072".into()];
-        let acc = client.query_orders(orders).await.unwrap();
+        let orders = ["575758889245571072", "575755030422977024"];
+        let acc = client.query_orders(&orders).await.unwrap();
         dbg!(acc);
 
         // deal columns for execute price info
         // taker_fee: 0.0049071
-    } */
+    }
+
+    #[test]
+    pub fn test_futures_encode_query() {
+        let params = Params::new()
+            .add("symbol", "BTC_USDT")
+            .add("order_ids", "1,2")
+            .add("note", "a b");
+
+        assert_eq!(MexcFutures::encode_query(params), "note=a%20b&order_ids=1%2C2&symbol=BTC_USDT");
+    }
 
     #[tokio::test]
     pub async fn test_futures_websocket_login() {
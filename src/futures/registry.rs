@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use crate::error::Result;
+use super::MexcFutures;
+use super::structures::ContractInfo;
+
+#[derive(Debug, Default)]
+struct Snapshot {
+    contracts: HashMap<String, Arc<ContractInfo>>,
+    loaded_at: Option<Instant>
+}
+
+/// In-memory copy of every `ContractInfo`, so sizing and validation don't need a round trip per order.
+#[derive(Debug)]
+pub struct ContractRegistry {
+    snapshot: RwLock<Snapshot>,
+    last_error: RwLock<Option<String>>,
+    refresh_interval: Duration
+}
+
+impl ContractRegistry {
+
+    /// Empty registry, call `refresh` or use `load` to populate it.
+    pub fn new(refresh_interval: Duration) -> Self {
+        Self {
+            snapshot: RwLock::new(Snapshot::default()),
+            last_error: RwLock::new(None),
+            refresh_interval
+        }
+    }
+
+    pub async fn load(client: &MexcFutures, refresh_interval: Duration) -> Result<Self> {
+        let registry = Self::new(refresh_interval);
+        registry.refresh(client).await?;
+        Ok(registry)
+    }
+
+    /// Replaces the snapshot with the current contract list, returns the number of contracts.
+    /// A failure is kept in `last_error` until the next successful refresh.
+    pub async fn refresh(&self, client: &MexcFutures) -> Result<usize> {
+        match client.get_all_contract_details().await {
+            Ok(details) => {
+                *self.last_error.write().unwrap() = None;
+                Ok(self.replace(details))
+            },
+            Err(err) => {
+                *self.last_error.write().unwrap() = Some(err.to_string());
+                Err(err)
+            }
+        }
+    }
+
+    fn replace(&self, details: Vec<ContractInfo>) -> usize {
+        let contracts: HashMap<String, Arc<ContractInfo>> = details
+            .into_iter()
+            .map(|info| (info.symbol.clone(), Arc::new(info)))
+            .collect();
+        let count = contracts.len();
+
+        *self.snapshot.write().unwrap() = Snapshot {
+            contracts,
+            loaded_at: Some(Instant::now())
+        };
+        count
+    }
+
+    pub fn is_stale(&self) -> bool {
+        match self.snapshot.read().unwrap().loaded_at {
+            Some(loaded_at) => loaded_at.elapsed() >= self.refresh_interval,
+            None => true
+        }
+    }
+
+    /// Refreshes when the snapshot is older than the refresh interval, returns whether it did.
+    pub async fn refresh_if_stale(&self, client: &MexcFutures) -> Result<bool> {
+        if !self.is_stale() {
+            return Ok(false);
+        }
+        self.refresh(client).await?;
+        Ok(true)
+    }
+
+    /// Refreshes in the background every refresh interval. A failed refresh keeps the previous snapshot
+    /// and shows up in `last_error`, with `is_stale` turning true once the snapshot outlives the interval.
+    pub fn spawn_refresh(self: &Arc<Self>, client: Arc<MexcFutures>) -> JoinHandle<()> {
+        let registry = Arc::clone(self);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(registry.refresh_interval);
+            loop {
+                interval.tick().await;
+                // recorded in last_error
+                let _ = registry.refresh(&client).await;
+            }
+        })
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<Arc<ContractInfo>> {
+        self.snapshot.read().unwrap().contracts.get(symbol).cloned()
+    }
+
+    pub fn by_base_coin(&self, base_coin: &str) -> Vec<Arc<ContractInfo>> {
+        self.filter(|info| info.base_coin.eq_ignore_ascii_case(base_coin))
+    }
+
+    pub fn by_settle_coin(&self, settle_coin: &str) -> Vec<Arc<ContractInfo>> {
+        self.filter(|info| info.settle_coin.eq_ignore_ascii_case(settle_coin))
+    }
+
+    pub fn symbols(&self) -> Vec<String> {
+        self.snapshot.read().unwrap().contracts.keys().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshot.read().unwrap().contracts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn loaded_at(&self) -> Option<Instant> {
+        self.snapshot.read().unwrap().loaded_at
+    }
+
+    /// Error of the last refresh, `None` once a refresh succeeds.
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.read().unwrap().clone()
+    }
+
+    fn filter(&self, predicate: impl Fn(&ContractInfo) -> bool) -> Vec<Arc<ContractInfo>> {
+        self.snapshot.read().unwrap().contracts
+            .values()
+            .filter(|info| predicate(info))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::futures::structures::ContractList;
+
+    const CONTRACTS: &str = r#"[
+        {"symbol":"BTC_USDT","baseCoin":"BTC","quoteCoin":"USDT","settleCoin":"USDT","contractSize":0.0001,"minVol":1,"maxVol":"1250000","priceUnit":0.1,"volUnit":1,"priceScale":1,"volScale":0,"state":0,"apiAllowed":true},
+        {"symbol":"BTC_USD","baseCoin":"BTC","quoteCoin":"USD","settleCoin":"BTC","contractSize":100,"minVol":1,"maxVol":50000,"priceUnit":0.5,"volUnit":1,"priceScale":1,"volScale":0},
+        {"symbol":"ETH_USDT","baseCoin":"ETH","quoteCoin":"USDT","settleCoin":"USDT","contractSize":0.01,"minVol":1,"maxVol":1000000,"priceUnit":0.01,"volUnit":1,"priceScale":2,"volScale":0,"displayName":null},
+        {"symbol":"NOSIZE_USDT","baseCoin":"NOSIZE","quoteCoin":"USDT","settleCoin":"USDT","minVol":1,"maxVol":1000,"priceUnit":0.01,"volUnit":1,"priceScale":2,"volScale":0},
+        {"symbol":"ZERO_USDT","baseCoin":"ZERO","quoteCoin":"USDT","settleCoin":"USDT","contractSize":null,"minVol":1,"maxVol":1000,"priceUnit":0.01,"volUnit":1,"priceScale":2,"volScale":0},
+        {"symbol":"BROKEN_USDT","baseCoin":null,"settleCoin":"USDT"}
+    ]"#;
+
+    fn fixture() -> Vec<ContractInfo> {
+        serde_json::from_str::<ContractList>(CONTRACTS).unwrap().0
+    }
+
+    #[test]
+    fn test_contract_registry_index() {
+        let registry = ContractRegistry::new(Duration::from_secs(60));
+        assert!(registry.is_stale());
+        assert!(registry.is_empty());
+
+        assert_eq!(registry.replace(fixture()), 3);
+        assert!(!registry.is_stale());
+        assert!(registry.last_error().is_none());
+
+        let btc = registry.get("BTC_USDT").unwrap();
+        assert_eq!(btc.contract_size, 0.0001);
+        assert_eq!(btc.max_vol, 1250000.0);
+        assert!(btc.api_allowed);
+        assert_eq!(registry.get("BTC_USD").unwrap().contract_size, 100.0);
+
+        // contracts that couldn't be sized are left out rather than served with a zero contract size
+        assert!(registry.get("NOSIZE_USDT").is_none());
+        assert!(registry.get("ZERO_USDT").is_none());
+        assert!(registry.get("BROKEN_USDT").is_none());
+
+        let mut btc_symbols: Vec<_> = registry.by_base_coin("btc").iter().map(|info| info.symbol.clone()).collect();
+        btc_symbols.sort();
+        assert_eq!(btc_symbols, ["BTC_USD", "BTC_USDT"]);
+
+        assert_eq!(registry.by_settle_coin("USDT").len(), 2);
+        assert_eq!(registry.by_settle_coin("BTC")[0].symbol, "BTC_USD");
+
+        let expired = ContractRegistry::new(Duration::ZERO);
+        expired.replace(vec![]);
+        assert!(expired.is_stale());
+    }
+
+    #[tokio::test]
+    async fn test_contract_registry_last_error() {
+        let client = MexcFutures::builder()
+            .base_url("http://127.0.0.1:1")
+            .retry_policy(crate::retry::RetryPolicy::disabled())
+            .build().unwrap();
+
+        let registry = ContractRegistry::new(Duration::from_secs(60));
+        registry.replace(fixture());
+
+        assert!(registry.refresh(&client).await.is_err());
+        assert!(registry.last_error().is_some());
+        assert!(registry.get("BTC_USDT").is_some());
+    }
+}
@@ -1,7 +1,7 @@
 use serde::Deserialize;
 use serde_json::Value;
 use serde_repr::Deserialize_repr;
-use crate::utils::parse_string_to_f64;
+use crate::utils::{Number, null_as_default, parse_optional_number, parse_positive_f64, parse_string_to_f64, parse_string_to_number, skip_unknown};
 use std::fmt;
 
 #[derive(Deserialize, Debug)]
@@ -9,50 +9,61 @@ pub struct FuturesBalance {
     pub currency: String,
 
     #[serde(rename = "positionMargin")]
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub position_margin: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub position_margin: Number,
 
     #[serde(rename = "availableBalance")]
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub available_balance: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub available_balance: Number,
 
     #[serde(rename = "cashBalance")]
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub cash_balance: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub cash_balance: Number,
 
     #[serde(rename = "frozenBalance")]
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub frozen_balance: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub frozen_balance: Number,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub equity: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub equity: Number,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub unrealized: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub unrealized: Number,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub bonus: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub bonus: Number,
 }
 
 
+/// Envelope of every futures response, `T` is the type of the `data` payload.
 #[derive(Deserialize, Debug)]
-pub struct FuturesResponse {
+pub struct FuturesResponse<T = Value> {
     pub success: bool,
     pub code: i64,
-    pub data: Option<Value>,
+    pub data: Option<T>,
     pub message: Option<String>
 }
 
+#[derive(Deserialize, Debug)]
+pub struct IndexPrice {
+    pub symbol: String,
+
+    #[serde(rename = "indexPrice", deserialize_with = "parse_string_to_number")]
+    pub index_price: Number,
+
+    pub timestamp: u128,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct FuturesPosition {
     #[serde(rename = "autoAddIm")]
     pub auto_add_im: bool,
 
-    #[serde(rename = "closeAvgPrice", deserialize_with = "parse_string_to_f64")]
-    pub close_avg_price: f64,
+    #[serde(rename = "closeAvgPrice", deserialize_with = "parse_string_to_number")]
+    pub close_avg_price: Number,
 
-    #[serde(rename = "closeProfitLoss", deserialize_with = "parse_string_to_f64")]
-    pub close_profit_loss: f64,
+    #[serde(rename = "closeProfitLoss", deserialize_with = "parse_string_to_number")]
+    pub close_profit_loss: Number,
 
     #[serde(rename = "closeVol", deserialize_with = "parse_string_to_f64")]
     pub close_vol: f64,
@@ -60,50 +71,51 @@ pub struct FuturesPosition {
     #[serde(rename = "createTime")]
     pub create_time: u128,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub fee: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub fee: Number,
 
     #[serde(rename = "frozenVol", deserialize_with = "parse_string_to_f64")]
     pub frozen_vol: f64,
 
-    #[serde(rename = "holdAvgPrice", deserialize_with = "parse_string_to_f64")]
-    pub hold_avg_price: f64,
+    #[serde(rename = "holdAvgPrice", deserialize_with = "parse_string_to_number")]
+    pub hold_avg_price: Number,
 
-    #[serde(rename = "holdAvgPriceFullyScale", deserialize_with = "parse_string_to_f64")]
-    pub hold_avg_price_fully_scale: f64,
+    #[serde(rename = "holdAvgPriceFullyScale", deserialize_with = "parse_string_to_number")]
+    pub hold_avg_price_fully_scale: Number,
 
-    #[serde(rename = "holdFee", deserialize_with = "parse_string_to_f64")]
-    pub hold_fee: f64,
+    #[serde(rename = "holdFee", deserialize_with = "parse_string_to_number")]
+    pub hold_fee: Number,
 
     #[serde(rename = "holdVol")]
     pub hold_vol: u64,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub im: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub im: Number,
     pub leverage: u64,
 
-    pub pnl: Option<f64>,
+    #[serde(default, deserialize_with = "parse_optional_number")]
+    pub pnl: Option<Number>,
 
-    #[serde(rename = "liquidatePrice", deserialize_with = "parse_string_to_f64")]
-    pub liquidate_price: f64,
+    #[serde(rename = "liquidatePrice", deserialize_with = "parse_string_to_number")]
+    pub liquidate_price: Number,
 
     #[serde(rename = "marginRatio", deserialize_with = "parse_string_to_f64")]
     pub margin_ratio: f64,
 
-    #[serde(rename = "newCloseAvgPrice", deserialize_with = "parse_string_to_f64")]
-    pub new_close_avg_price: f64,
+    #[serde(rename = "newCloseAvgPrice", deserialize_with = "parse_string_to_number")]
+    pub new_close_avg_price: Number,
 
-    #[serde(rename = "newOpenAvgPrice", deserialize_with = "parse_string_to_f64")]
-    pub new_open_avg_price: f64,
+    #[serde(rename = "newOpenAvgPrice", deserialize_with = "parse_string_to_number")]
+    pub new_open_avg_price: Number,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub oim: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub oim: Number,
 
-    #[serde(rename = "openAvgPrice", deserialize_with = "parse_string_to_f64")]
-    pub open_avg_price: f64,
+    #[serde(rename = "openAvgPrice", deserialize_with = "parse_string_to_number")]
+    pub open_avg_price: Number,
 
-    #[serde(rename = "openAvgPriceFullyScale", deserialize_with = "parse_string_to_f64")]
-    pub open_avg_price_fully_scale: f64,
+    #[serde(rename = "openAvgPriceFullyScale", deserialize_with = "parse_string_to_number")]
+    pub open_avg_price_fully_scale: Number,
 
     #[serde(rename = "openType")]
     pub open_type: OpenType,
@@ -116,8 +128,8 @@ pub struct FuturesPosition {
 
     pub profit_ratio: Option<String>,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub realised: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub realised: Number,
 
     pub state: i32,
 
@@ -130,173 +142,182 @@ pub struct FuturesPosition {
 }
 
 
+/// Descriptive fields fall back to their defaults when missing or null. The identity and sizing fields are required,
+/// with the sizing ones positive, since orders are sized by dividing by them.
 #[derive(Deserialize, Debug)]
 pub struct ContractInfo {
-    #[serde(rename = "amountScale")]
+    #[serde(rename = "amountScale", deserialize_with = "null_as_default", default)]
     pub amount_scale: i32,
 
-    #[serde(rename = "apiAllowed")]
+    #[serde(rename = "apiAllowed", deserialize_with = "null_as_default", default)]
     pub api_allowed: bool,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
+    #[serde(deserialize_with = "parse_string_to_f64", default)]
     pub appraisal: f64,
 
-    #[serde(rename = "askLimitPriceRate", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "askLimitPriceRate", deserialize_with = "parse_string_to_f64", default)]
     pub ask_limit_price_rate: f64,
 
-    #[serde(rename = "automaticDelivery")]
+    #[serde(rename = "automaticDelivery", deserialize_with = "null_as_default", default)]
     pub automatic_delivery: i32,
 
     #[serde(rename = "baseCoin")]
     pub base_coin: String,
 
-    #[serde(rename = "baseCoinIconUrl")]
+    #[serde(rename = "baseCoinIconUrl", deserialize_with = "null_as_default", default)]
     pub base_coin_icon_url: String,
 
-    #[serde(rename = "baseCoinId")]
+    #[serde(rename = "baseCoinId", deserialize_with = "null_as_default", default)]
     pub base_coin_id: String,
 
-    #[serde(rename = "baseCoinName")]
+    #[serde(rename = "baseCoinName", deserialize_with = "null_as_default", default)]
     pub base_coin_name: String,
 
-    #[serde(rename = "bidLimitPriceRate", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "bidLimitPriceRate", deserialize_with = "parse_string_to_f64", default)]
     pub bid_limit_price_rate: f64,
 
-    #[serde(rename = "conceptPlate")]
+    #[serde(rename = "conceptPlate", deserialize_with = "null_as_default", default)]
     pub concept_plate: Vec<String>,
 
-    #[serde(rename = "contractSize", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "contractSize", deserialize_with = "parse_positive_f64")]
     pub contract_size: f64,
 
-    #[serde(rename = "depthStepList")]
+    #[serde(rename = "depthStepList", deserialize_with = "null_as_default", default)]
     pub depth_step_list: Vec<String>,
 
-    #[serde(rename = "displayName")]
+    #[serde(rename = "displayName", deserialize_with = "null_as_default", default)]
     pub display_name: String,
 
-    #[serde(rename = "displayNameEn")]
+    #[serde(rename = "displayNameEn", deserialize_with = "null_as_default", default)]
     pub display_name_en: String,
 
-    #[serde(rename = "futureType")]
+    #[serde(rename = "futureType", deserialize_with = "null_as_default", default)]
     pub future_type: i32,
 
+    #[serde(deserialize_with = "null_as_default", default)]
     pub id: i64,
 
-    #[serde(rename = "indexOrigin")]
+    #[serde(rename = "indexOrigin", deserialize_with = "null_as_default", default)]
     pub index_origin: Vec<String>,
 
-    #[serde(rename = "initialMarginRate", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "initialMarginRate", deserialize_with = "parse_string_to_f64", default)]
     pub initial_margin_rate: f64,
 
-    #[serde(rename = "isHidden")]
+    #[serde(rename = "isHidden", deserialize_with = "null_as_default", default)]
     pub is_hidden: bool,
 
-    #[serde(rename = "isHot")]
+    #[serde(rename = "isHot", deserialize_with = "null_as_default", default)]
     pub is_hot: bool,
 
-    #[serde(rename = "isNew")]
+    #[serde(rename = "isNew", deserialize_with = "null_as_default", default)]
     pub is_new: bool,
 
-    #[serde(rename = "limitMaxVol", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "limitMaxVol", deserialize_with = "parse_string_to_f64", default)]
     pub limit_max_vol: f64,
 
-    #[serde(rename = "maintenanceMarginRate", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "maintenanceMarginRate", deserialize_with = "parse_string_to_f64", default)]
     pub maintenance_margin_rate: f64,
 
-    #[serde(rename = "makerFeeRate", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "makerFeeRate", deserialize_with = "parse_string_to_f64", default)]
     pub maker_fee_rate: f64,
 
-    #[serde(rename = "marketOrderMaxLevel")]
+    #[serde(rename = "marketOrderMaxLevel", deserialize_with = "null_as_default", default)]
     pub market_order_max_level: i32,
 
-    #[serde(rename = "marketOrderPriceLimitRate1", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "marketOrderPriceLimitRate1", deserialize_with = "parse_string_to_f64", default)]
     pub market_order_price_limit_rate1: f64,
 
-    #[serde(rename = "marketOrderPriceLimitRate2", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "marketOrderPriceLimitRate2", deserialize_with = "parse_string_to_f64", default)]
     pub market_order_price_limit_rate2: f64,
 
-    #[serde(rename = "maxLeverage")]
+    #[serde(rename = "maxLeverage", deserialize_with = "null_as_default", default)]
     pub max_leverage: i32,
 
-    #[serde(rename = "maxNumOrders")]
+    #[serde(rename = "maxNumOrders", deserialize_with = "null_as_default", default)]
     pub max_num_orders: Vec<i32>,
 
-    #[serde(rename = "maxVol", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "maxVol", deserialize_with = "parse_positive_f64")]
     pub max_vol: f64,
 
-    #[serde(rename = "minLeverage")]
+    #[serde(rename = "minLeverage", deserialize_with = "null_as_default", default)]
     pub min_leverage: i32,
 
-    #[serde(rename = "minVol", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "minVol", deserialize_with = "parse_positive_f64")]
     pub min_vol: f64,
 
-    #[serde(rename = "positionOpenType")]
+    #[serde(rename = "positionOpenType", deserialize_with = "null_as_default", default)]
     pub position_open_type: i32,
 
-    #[serde(rename = "priceCoefficientVariation", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "priceCoefficientVariation", deserialize_with = "parse_string_to_f64", default)]
     pub price_coefficient_variation: f64,
 
     #[serde(rename = "priceScale")]
     pub price_scale: i32,
 
-    #[serde(rename = "priceUnit", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "priceUnit", deserialize_with = "parse_positive_f64")]
     pub price_unit: f64,
 
     #[serde(rename = "quoteCoin")]
     pub quote_coin: String,
 
-    #[serde(rename = "quoteCoinName")]
+    #[serde(rename = "quoteCoinName", deserialize_with = "null_as_default", default)]
     pub quote_coin_name: String,
 
-    #[serde(rename = "riskBaseVol", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "riskBaseVol", deserialize_with = "parse_string_to_f64", default)]
     pub risk_base_vol: f64,
 
-    #[serde(rename = "riskIncrImr", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "riskIncrImr", deserialize_with = "parse_string_to_f64", default)]
     pub risk_incr_imr: f64,
 
-    #[serde(rename = "riskIncrMmr", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "riskIncrMmr", deserialize_with = "parse_string_to_f64", default)]
     pub risk_incr_mmr: f64,
 
-    #[serde(rename = "riskIncrVol", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "riskIncrVol", deserialize_with = "parse_string_to_f64", default)]
     pub risk_incr_vol: f64,
 
-    #[serde(rename = "riskLevelLimit")]
+    #[serde(rename = "riskLevelLimit", deserialize_with = "null_as_default", default)]
     pub risk_level_limit: i32,
 
-    #[serde(rename = "riskLimitType")]
+    #[serde(rename = "riskLimitType", deserialize_with = "null_as_default", default)]
     pub risk_limit_type: String,
 
-    #[serde(rename = "riskLongShortSwitch")]
+    #[serde(rename = "riskLongShortSwitch", deserialize_with = "null_as_default", default)]
     pub risk_long_short_switch: i32,
 
     #[serde(rename = "settleCoin")]
     pub settle_coin: String,
 
-    #[serde(rename = "showAppraisalCountdown")]
+    #[serde(rename = "showAppraisalCountdown", deserialize_with = "null_as_default", default)]
     pub show_appraisal_countdown: i32,
 
+    #[serde(deserialize_with = "null_as_default", default)]
     pub state: i32,
 
     pub symbol: String,
 
-    #[serde(rename = "takerFeeRate", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "takerFeeRate", deserialize_with = "parse_string_to_f64", default)]
     pub taker_fee_rate: f64,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
+    #[serde(deserialize_with = "parse_string_to_f64", default)]
     pub threshold: f64,
 
-    #[serde(rename = "triggerProtect", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "triggerProtect", deserialize_with = "parse_string_to_f64", default)]
     pub trigger_protect: f64,
 
+    #[serde(deserialize_with = "null_as_default", default)]
     pub vid: String,
 
     #[serde(rename = "volScale")]
     pub vol_scale: i32,
 
-    #[serde(rename = "volUnit", deserialize_with = "parse_string_to_f64")]
+    #[serde(rename = "volUnit", deserialize_with = "parse_positive_f64")]
     pub vol_unit: f64,
 }
 
+/// Contract list that drops entries which don't decode, e.g. missing or zero sizing fields.
+#[derive(Deserialize, Debug)]
+pub(crate) struct ContractList(#[serde(deserialize_with = "skip_unknown")] pub Vec<ContractInfo>);
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct OrderReceipt {
     #[serde(rename = "orderId")]
@@ -373,8 +394,8 @@ pub struct FuturesOrder {
     pub category: i64,
     #[serde(rename = "createTime")]
     pub create_time: u128,
-    #[serde(rename = "dealAvgPrice", deserialize_with = "parse_string_to_f64")]
-    pub deal_avg_price: f64,
+    #[serde(rename = "dealAvgPrice", deserialize_with = "parse_string_to_number")]
+    pub deal_avg_price: Number,
     #[serde(rename = "dealVol")]
     pub deal_vol: u64,
     #[serde(rename = "errorCode")]
@@ -384,32 +405,33 @@ pub struct FuturesOrder {
     #[serde(rename = "feeCurrency")]
     pub fee_currency: String,
     pub leverage: u64,
-    #[serde(rename = "makerFee")]
-    pub maker_fee: f64,
+    #[serde(rename = "makerFee", deserialize_with = "parse_string_to_number")]
+    pub maker_fee: Number,
     #[serde(rename = "openType")]
     pub open_type: OpenType,
     #[serde(rename = "orderId")]
     pub order_id: String,
-    #[serde(rename = "orderMargin", deserialize_with = "parse_string_to_f64")]
-    pub order_margin: f64,
+    #[serde(rename = "orderMargin", deserialize_with = "parse_string_to_number")]
+    pub order_margin: Number,
     #[serde(rename = "orderType")]
     pub order_type: OrderType,
     #[serde(rename = "positionId")]
     pub position_id: i64,
     #[serde(rename = "positionMode")]
     pub position_mode: i64,
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub price: f64,
-    pub profit: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub price: Number,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub profit: Number,
     pub side: OrderDirection,
     pub state: i64,
     pub symbol: String,
-    #[serde(rename = "takerFee")]
-    pub taker_fee: f64,
+    #[serde(rename = "takerFee", deserialize_with = "parse_string_to_number")]
+    pub taker_fee: Number,
     #[serde(rename = "updateTime")]
     pub update_time: u128,
-    #[serde(rename = "usedMargin")]
-    pub used_margin: f64,
+    #[serde(rename = "usedMargin", deserialize_with = "parse_string_to_number")]
+    pub used_margin: Number,
     pub version: i64,
     pub vol: i64,
 }
@@ -433,12 +455,14 @@ pub struct FuturesDeal {
 
     #[serde(rename = "positionMode")]
     pub position_mode: i64,
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub price: f64,
-    pub profit: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub price: Number,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub profit: Number,
     pub side: OrderDirection,
     pub symbol: String,
-    pub fee: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub fee: Number,
     pub timestamp: u128,
     pub vol: u64,
 }
@@ -448,19 +472,19 @@ pub struct FuturesAsset {
     pub currency: String,
 
     #[serde(rename = "positionMargin")]
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub position_margin: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub position_margin: Number,
 
     #[serde(rename = "availableBalance")]
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub available_balance: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub available_balance: Number,
 
     #[serde(rename = "frozenBalance")]
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub frozen_balance: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub frozen_balance: Number,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub bonus: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub bonus: Number,
 }
 
 #[cfg(test)]
@@ -471,8 +495,19 @@ mod tests {
     #[test]
     fn test_decode_structure() {
         let json = r#""#;
-        let data: FuturesPosition = serde_json::from_str(&json).unwrap();
+        let data: FuturesPosition = serde_json::from_str(json).unwrap();
         dbg!(data);
     }
 
+    #[test]
+    fn test_decode_generic_response() {
+        let json = r#"{"success":true,"code":0,"data":1587442022003}"#;
+        let resp: FuturesResponse<u128> = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.data, Some(1587442022003));
+
+        let json = r#"{"success":false,"code":602,"message":"Signature verification failed!"}"#;
+        let resp: FuturesResponse<Vec<FuturesBalance>> = serde_json::from_str(json).unwrap();
+        assert!(!resp.success && resp.data.is_none());
+    }
+
 }
\ No newline at end of file
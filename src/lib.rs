@@ -1,13 +1,34 @@
+pub mod error;
+pub mod error_codes;
 pub mod utils;
 pub mod market;
+pub mod registry;
+pub mod validation;
 pub mod orders;
 pub mod testing;
 pub mod account;
+pub mod wallet;
+pub mod sub_account;
 pub mod futures;
+pub mod time_sync;
+pub mod rate_limit;
+pub mod retry;
+mod request;
 
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use reqwest::Client;
 use serde::Deserialize;
+use error::Result;
+use utils::{decode_response, get_timestamp};
+use time_sync::{ClockOffset, TimeSync, DEFAULT_TIME_SYNC_INTERVAL};
+use rate_limit::{RateLimiter, RateLimitStats, TokenBucketLimiter, spot_request_weights};
+use retry::RetryPolicy;
+use wallet::WithdrawPolicy;
+use request::Params;
+
+pub use error::MexcError;
+pub use error_codes::ErrorCode;
 
 pub const PROD_API_URL: &str = "https://api.mexc.com";
 
@@ -15,6 +36,11 @@ pub const PROD_API_URL: &str = "https://api.mexc.com";
 pub struct Mexc {
     pub api_key: Option<String>,
     pub api_secret: Option<String>,
+    pub base_url: String,
+    pub time_sync: TimeSync,
+    pub rate_limiter: Arc<dyn RateLimiter>,
+    pub retry_policy: RetryPolicy,
+    pub withdraw_policy: Option<WithdrawPolicy>,
     pub client: Client
 }
 
@@ -27,11 +53,69 @@ pub struct ServerTime {
     pub timestamp: u128
 }
 
-impl Mexc {
+#[derive(Default, Clone)]
+pub struct MexcBuilder {
+    api_key: Option<String>,
+    api_secret: Option<String>,
+    proxy_url: Option<String>,
+    base_url: Option<String>,
+    time_sync_interval: Option<Duration>,
+    rate_limiter: Option<Arc<dyn RateLimiter>>,
+    retry_policy: Option<RetryPolicy>,
+    withdraw_policy: Option<WithdrawPolicy>
+}
+
+impl MexcBuilder {
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
 
-    pub fn new(api_key: Option<String>, api_secret: Option<String>, proxy_url: Option<String>) -> anyhow::Result<Self> {
+    pub fn api_secret(mut self, api_secret: impl Into<String>) -> Self {
+        self.api_secret = Some(api_secret.into());
+        self
+    }
+
+    pub fn proxy_url(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy_url = Some(proxy_url.into());
+        self
+    }
+
+    /// Spot REST host, defaults to `PROD_API_URL`. Useful for mock servers and regional mirrors.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
 
-        let client = match proxy_url {
+    /// Resync the clock offset against `get_server_time` when older than `interval`, defaults to `DEFAULT_TIME_SYNC_INTERVAL`.
+    pub fn time_sync_interval(mut self, interval: Duration) -> Self {
+        self.time_sync_interval = Some(interval);
+        self
+    }
+
+    /// Replaces the default `TokenBucketLimiter`, share one limiter between clients on the same IP.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<dyn RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Defaults to `RetryPolicy::default()`, use `RetryPolicy::disabled()` to send every request once.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Checks every `withdraw` against `policy` before it is sent. Without one, withdrawals are only
+    /// limited by the API key permissions.
+    pub fn withdraw_policy(mut self, policy: WithdrawPolicy) -> Self {
+        self.withdraw_policy = Some(policy);
+        self
+    }
+
+    pub fn build(self) -> Result<Mexc> {
+
+        let client = match self.proxy_url {
             Some(url) => {
                 let proxy = reqwest::Proxy::all(url)?;
                 reqwest::Client::builder().proxy(proxy).build()?
@@ -39,28 +123,78 @@ impl Mexc {
             None => reqwest::Client::new()
         };
 
+        let base_url = utils::trim_base_url(self.base_url.as_deref().unwrap_or(PROD_API_URL));
 
-        Ok(Self {
-            api_key,
-            api_secret,
+        Ok(Mexc {
+            api_key: self.api_key,
+            api_secret: self.api_secret,
+            base_url,
+            time_sync: TimeSync::new(Some(self.time_sync_interval.unwrap_or(DEFAULT_TIME_SYNC_INTERVAL))),
+            rate_limiter: self.rate_limiter.unwrap_or_else(|| Arc::new(TokenBucketLimiter::default())),
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            withdraw_policy: self.withdraw_policy,
             client
         })
     }
+}
+
+impl Mexc {
 
-    pub async fn get_server_time(&self) -> anyhow::Result<u128> {
-        let url = format!("{PROD_API_URL}/api/v3/time");
-        let resp = self.client.get(url).send().await?;
+    pub fn new(api_key: Option<String>, api_secret: Option<String>, proxy_url: Option<String>) -> Result<Self> {
+        MexcBuilder {
+            api_key,
+            api_secret,
+            proxy_url,
+            ..Default::default()
+        }.build()
+    }
+
+    pub fn builder() -> MexcBuilder {
+        MexcBuilder::default()
+    }
+
+    pub fn rate_limit_stats(&self) -> RateLimitStats {
+        self.rate_limiter.stats()
+    }
 
-        let st: ServerTime = resp.json().await?;
+    pub async fn get_server_time(&self) -> Result<u128> {
+        let st: ServerTime = self.public_request("/api/v3/time", Params::new()).await?;
         Ok(st.timestamp)
     }
 
-    pub async fn ping(&self) -> anyhow::Result<Duration> {
-        let url = format!("{PROD_API_URL}/api/v3/ping");
+    /// Measures the clock offset against the server and applies it to subsequent signed requests.
+    /// The probe is charged to the rate limiter but bypasses retries, and only the http exchange is timed,
+    /// since a limiter wait or backoff inside the round trip would skew the midpoint.
+    pub async fn sync_time(&self) -> Result<ClockOffset> {
+        let path = "/api/v3/time";
+        self.rate_limiter.acquire(&spot_request_weights(path, false, false)).await;
 
+        let sent_at = get_timestamp();
         let inst = Instant::now();
-        let _ = self.client.get(url).send().await?;
+        let resp = self.client.get(format!("{}{path}", self.base_url)).send().await?;
+        let round_trip = inst.elapsed();
+
+        let server_time: ServerTime = decode_response(resp).await?;
+        Ok(self.time_sync.record(sent_at, round_trip, server_time.timestamp))
+    }
+
+    pub fn clock_offset(&self) -> ClockOffset {
+        self.time_sync.offset()
+    }
+
+    /// Corrected timestamp for signed requests, resyncing first when the offset is stale.
+    /// Falls back to the last offset when the resync fails.
+    pub async fn timestamp(&self) -> Result<u128> {
+        self.time_sync.sync_if_needed(|| self.sync_time()).await?;
+        Ok(self.time_sync.now())
+    }
+
+    pub async fn ping(&self) -> Result<Duration> {
+        let url = format!("{}/api/v3/ping", self.base_url);
+
+        let inst = Instant::now();
+        let _ = self.send(self.client.get(url)).await?;
 
         Ok(inst.elapsed())
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,4 @@
-use crate::{Mexc, PROD_API_URL, utils::parse_string_to_f64};
+use crate::{Mexc, MexcError, error::Result, request::Params, orders::OrderType, utils::{Number, ZERO, parse_number, parse_string_to_f64, parse_string_to_number, skip_unknown, step_size}};
 use serde::Deserialize;
 use serde::de::{self, Visitor, SeqAccess};
 use std::fmt;
@@ -13,7 +13,7 @@ pub struct Orderbook {
     pub asks: Vec<Level>
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct ExchangeInfo {
     #[serde(rename= "serverTime")]
     pub timestamp: u128,
@@ -21,7 +21,7 @@ pub struct ExchangeInfo {
 }
 
 #[allow(dead_code)]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct SymbolInfo {
     #[serde(rename = "baseAsset")]
     pub base_asset: String,
@@ -32,11 +32,11 @@ pub struct SymbolInfo {
     #[serde(rename = "baseCommissionPrecision")]
     pub base_commission_precision: u32,
     
-    #[serde(rename = "baseSizePrecision", deserialize_with = "parse_string_to_f64")]
-    pub base_size_precision: f64,
+    #[serde(rename = "baseSizePrecision", deserialize_with = "parse_string_to_number")]
+    pub base_size_precision: Number,
     
-    #[serde(rename = "filters")]
-    pub filters: Vec<String>,
+    #[serde(rename = "filters", deserialize_with = "skip_unknown")]
+    pub filters: Vec<SymbolFilter>,
     
     #[serde(rename = "fullName")]
     pub full_name: String,
@@ -50,23 +50,23 @@ pub struct SymbolInfo {
     #[serde(rename = "makerCommission", deserialize_with = "parse_string_to_f64")]
     pub maker_commission: f64,
     
-    #[serde(rename = "maxQuoteAmount", deserialize_with = "parse_string_to_f64")]
-    pub max_quote_amount: f64,
+    #[serde(rename = "maxQuoteAmount", deserialize_with = "parse_string_to_number")]
+    pub max_quote_amount: Number,
     
-    #[serde(rename = "maxQuoteAmountMarket", deserialize_with = "parse_string_to_f64")]
-    pub max_quote_amount_market: f64,
+    #[serde(rename = "maxQuoteAmountMarket", deserialize_with = "parse_string_to_number")]
+    pub max_quote_amount_market: Number,
     
-    #[serde(rename = "orderTypes")]
-    pub order_types: Vec<String>,
+    #[serde(rename = "orderTypes", deserialize_with = "skip_unknown")]
+    pub order_types: Vec<OrderType>,
     
     #[serde(rename = "permissions")]
     pub permissions: Vec<String>,
     
-    #[serde(rename = "quoteAmountPrecision", deserialize_with = "parse_string_to_f64")]
-    pub quote_amount_precision: f64,
+    #[serde(rename = "quoteAmountPrecision", deserialize_with = "parse_string_to_number")]
+    pub quote_amount_precision: Number,
     
-    #[serde(rename = "quoteAmountPrecisionMarket", deserialize_with = "parse_string_to_f64")]
-    pub quote_amount_precision_market: f64,
+    #[serde(rename = "quoteAmountPrecisionMarket", deserialize_with = "parse_string_to_number")]
+    pub quote_amount_precision_market: Number,
     
     #[serde(rename = "quoteAsset")]
     pub quote_asset: String,
@@ -81,7 +81,7 @@ pub struct SymbolInfo {
     pub quote_precision: u32,
     
     #[serde(rename = "status")]
-    pub status: String,
+    pub status: SymbolStatus,
     
     #[serde(rename = "symbol")]
     pub symbol: String,
@@ -90,14 +90,98 @@ pub struct SymbolInfo {
     pub taker_commission: f64,
 }
 
+/// Trading status, MEXC sends "1" / "2" / "3".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolStatus {
+    Online,
+    Paused,
+    Offline,
+    Unknown(String)
+}
+
+impl<'de> Deserialize<'de> for SymbolStatus {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let status: String = Deserialize::deserialize(deserializer)?;
+        Ok(match status.as_str() {
+            "1" | "ENABLED" => SymbolStatus::Online,
+            "2" | "PAUSE" => SymbolStatus::Paused,
+            "3" | "OFFLINE" => SymbolStatus::Offline,
+            _ => SymbolStatus::Unknown(status)
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "filterType")]
+pub enum SymbolFilter {
+    #[serde(rename = "PERCENT_PRICE_BY_SIDE")]
+    PercentPriceBySide {
+        #[serde(rename = "bidMultiplierUp", default, deserialize_with = "parse_string_to_f64")]
+        bid_multiplier_up: f64,
+        #[serde(rename = "bidMultiplierDown", default, deserialize_with = "parse_string_to_f64")]
+        bid_multiplier_down: f64,
+        #[serde(rename = "askMultiplierUp", default, deserialize_with = "parse_string_to_f64")]
+        ask_multiplier_up: f64,
+        #[serde(rename = "askMultiplierDown", default, deserialize_with = "parse_string_to_f64")]
+        ask_multiplier_down: f64,
+    },
+    #[serde(other)]
+    Other
+}
+
+/// Price and size constraints of a symbol.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymbolRules {
+    /// Smallest price increment, from `quote_precision`
+    pub price_tick: Number,
+    /// Smallest quantity increment, `base_size_precision` or `base_asset_precision` when that is 0
+    pub quantity_step: Number,
+    /// Minimum order value in the quote asset, from `quote_amount_precision`
+    pub min_notional: Number,
+    pub min_notional_market: Number,
+    pub max_notional: Number,
+    pub max_notional_market: Number
+}
+
+impl SymbolInfo {
+
+    pub fn rules(&self) -> SymbolRules {
+        let quantity_step = if self.base_size_precision > ZERO {
+            self.base_size_precision
+        } else {
+            step_size(self.base_asset_precision)
+        };
+
+        SymbolRules {
+            price_tick: step_size(self.quote_precision),
+            quantity_step,
+            min_notional: self.quote_amount_precision,
+            min_notional_market: self.quote_amount_precision_market,
+            max_notional: self.max_quote_amount,
+            max_notional_market: self.max_quote_amount_market
+        }
+    }
+
+    pub fn is_trading(&self) -> bool {
+        self.status == SymbolStatus::Online && self.is_spot_trading_allowed
+    }
+
+    pub fn supports(&self, order_type: OrderType) -> bool {
+        self.order_types.contains(&order_type)
+    }
+}
+
 #[derive(Debug)]
 pub struct Level {
-    pub px: f64,
-    pub sz: f64
+    pub px: Number,
+    pub sz: Number
 }
 
 impl<'de> Deserialize<'de> for Level {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -110,7 +194,7 @@ impl<'de> Deserialize<'de> for Level {
                 formatter.write_str("a two-element array [px, sz]")
             }
 
-            fn visit_seq<V>(self, mut seq: V) -> Result<Level, V::Error>
+            fn visit_seq<V>(self, mut seq: V) -> std::result::Result<Level, V::Error>
             where
                 V: SeqAccess<'de>,
             {
@@ -121,8 +205,8 @@ impl<'de> Deserialize<'de> for Level {
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(1, &self))?;
 
-                let px: f64 = px.parse().map_err(de::Error::custom)?;
-                let sz: f64 = sz.parse().map_err(de::Error::custom)?;
+                let px = parse_number(&px).map_err(de::Error::custom)?;
+                let sz = parse_number(&sz).map_err(de::Error::custom)?;
 
                 Ok(Level { px, sz })
             }
@@ -132,36 +216,469 @@ impl<'de> Deserialize<'de> for Level {
     }
 }
 
-impl Mexc {
+/// Candlestick intervals supported by `/api/v3/klines`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interval {
+    OneMinute,
+    FiveMinutes,
+    FifteenMinutes,
+    ThirtyMinutes,
+    SixtyMinutes,
+    FourHours,
+    OneDay,
+    OneWeek,
+    OneMonth
+}
 
-    pub async fn symbol_info(&self, symbol: &str) -> anyhow::Result<ExchangeInfo> {
-        let url = format!("{PROD_API_URL}/api/v3/exchangeInfo?symbol={symbol}");
-        let resp = self.client.get(url).send().await?;
+impl Interval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::OneMinute => "1m",
+            Interval::FiveMinutes => "5m",
+            Interval::FifteenMinutes => "15m",
+            Interval::ThirtyMinutes => "30m",
+            Interval::SixtyMinutes => "60m",
+            Interval::FourHours => "4h",
+            Interval::OneDay => "1d",
+            Interval::OneWeek => "1W",
+            Interval::OneMonth => "1M",
+        }
+    }
+}
 
-        let exchange_info: ExchangeInfo = resp.json().await?;
-        Ok(exchange_info)
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
     }
+}
+
+/// Max candles per `/api/v3/klines` request.
+pub const KLINES_LIMIT: u32 = 1000;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "RawKline")]
+pub struct Kline {
+    pub open_time: u128,
+    pub open: Number,
+    pub high: Number,
+    pub low: Number,
+    pub close: Number,
+    pub volume: Number,
+    pub close_time: u128,
+    pub quote_volume: Number
+}
 
-    pub async fn exchange_info(&self) -> anyhow::Result<ExchangeInfo> {
-        let url = format!("{PROD_API_URL}/api/v3/exchangeInfo");
-        let resp = self.client.get(url).send().await?;
+/// MEXC sends candles as `[openTime, open, high, low, close, volume, closeTime, quoteVolume]`.
+#[derive(Deserialize)]
+struct RawKline(
+    u128,
+    #[serde(deserialize_with = "parse_string_to_number")] Number,
+    #[serde(deserialize_with = "parse_string_to_number")] Number,
+    #[serde(deserialize_with = "parse_string_to_number")] Number,
+    #[serde(deserialize_with = "parse_string_to_number")] Number,
+    #[serde(deserialize_with = "parse_string_to_number")] Number,
+    u128,
+    #[serde(deserialize_with = "parse_string_to_number")] Number
+);
 
-        let exchange_info: ExchangeInfo = resp.json().await?;
-        Ok(exchange_info)
+impl From<RawKline> for Kline {
+    fn from(raw: RawKline) -> Self {
+        Kline {
+            open_time: raw.0,
+            open: raw.1,
+            high: raw.2,
+            low: raw.3,
+            close: raw.4,
+            volume: raw.5,
+            close_time: raw.6,
+            quote_volume: raw.7
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Trade {
+    pub id: Option<u64>,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub price: Number,
+    #[serde(rename = "qty", deserialize_with = "parse_string_to_number")]
+    pub quantity: Number,
+    #[serde(rename = "quoteQty", deserialize_with = "parse_string_to_number")]
+    pub quote_quantity: Number,
+    pub time: u128,
+    #[serde(rename = "isBuyerMaker")]
+    pub is_buyer_maker: bool,
+    #[serde(rename = "isBestMatch")]
+    pub is_best_match: bool
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AggTrade {
+    #[serde(rename = "a")]
+    pub id: Option<u64>,
+    #[serde(rename = "f")]
+    pub first_trade_id: Option<u64>,
+    #[serde(rename = "l")]
+    pub last_trade_id: Option<u64>,
+    #[serde(rename = "p", deserialize_with = "parse_string_to_number")]
+    pub price: Number,
+    #[serde(rename = "q", deserialize_with = "parse_string_to_number")]
+    pub quantity: Number,
+    #[serde(rename = "T")]
+    pub time: u128,
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+    #[serde(rename = "M")]
+    pub is_best_match: bool
+}
+
+/// Max trades per `/api/v3/aggTrades` request.
+pub const AGG_TRADES_LIMIT: u32 = 1000;
+
+/// `/api/v3/aggTrades` only accepts a `startTime`..`endTime` window of up to one hour.
+pub const AGG_TRADES_WINDOW_MS: u128 = 3600 * 1000;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Ticker24hr {
+    pub symbol: String,
+    #[serde(rename = "priceChange", deserialize_with = "parse_string_to_number")]
+    pub price_change: Number,
+    #[serde(rename = "priceChangePercent", deserialize_with = "parse_string_to_f64")]
+    pub price_change_percent: f64,
+    #[serde(rename = "prevClosePrice", deserialize_with = "parse_string_to_number")]
+    pub prev_close_price: Number,
+    #[serde(rename = "lastPrice", deserialize_with = "parse_string_to_number")]
+    pub last_price: Number,
+    #[serde(rename = "bidPrice", deserialize_with = "parse_string_to_number")]
+    pub bid_price: Number,
+    #[serde(rename = "bidQty", deserialize_with = "parse_string_to_number")]
+    pub bid_qty: Number,
+    #[serde(rename = "askPrice", deserialize_with = "parse_string_to_number")]
+    pub ask_price: Number,
+    #[serde(rename = "askQty", deserialize_with = "parse_string_to_number")]
+    pub ask_qty: Number,
+    #[serde(rename = "openPrice", deserialize_with = "parse_string_to_number")]
+    pub open_price: Number,
+    #[serde(rename = "highPrice", deserialize_with = "parse_string_to_number")]
+    pub high_price: Number,
+    #[serde(rename = "lowPrice", deserialize_with = "parse_string_to_number")]
+    pub low_price: Number,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub volume: Number,
+    #[serde(rename = "quoteVolume", deserialize_with = "parse_string_to_number")]
+    pub quote_volume: Number,
+    // u64 as untagged decoding in `OneOrMany` can't buffer u128
+    #[serde(rename = "openTime")]
+    pub open_time: u64,
+    #[serde(rename = "closeTime")]
+    pub close_time: u64,
+    pub count: Option<u64>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PriceTicker {
+    pub symbol: String,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub price: Number
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BookTicker {
+    pub symbol: String,
+    #[serde(rename = "bidPrice", deserialize_with = "parse_string_to_number")]
+    pub bid_price: Number,
+    #[serde(rename = "bidQty", deserialize_with = "parse_string_to_number")]
+    pub bid_qty: Number,
+    #[serde(rename = "askPrice", deserialize_with = "parse_string_to_number")]
+    pub ask_price: Number,
+    #[serde(rename = "askQty", deserialize_with = "parse_string_to_number")]
+    pub ask_qty: Number
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AvgPrice {
+    /// Averaging window in minutes
+    pub mins: u32,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub price: Number
+}
+
+/// Ticker endpoints return an object for one symbol and an array without one.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>)
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(item) => vec![item],
+            OneOrMany::Many(items) => items
+        }
     }
+}
 
-    pub async fn get_spot_orderbook(&self, symbol: &str, depth: Option<u32>) -> anyhow::Result<Orderbook> {
+impl Mexc {
+
+    pub async fn symbol_info(&self, symbol: &str) -> Result<ExchangeInfo> {
+        let params = Params::new().add("symbol", symbol);
+        self.public_request("/api/v3/exchangeInfo", params).await
+    }
+
+    pub async fn exchange_info(&self) -> Result<ExchangeInfo> {
+        self.public_request("/api/v3/exchangeInfo", Params::new()).await
+    }
+
+    pub async fn get_spot_orderbook(&self, symbol: &str, depth: Option<u32>) -> Result<Orderbook> {
 
         // limit: default 100; max 5000
 
-        let url = if let Some(limit) = depth {
-            format!("{PROD_API_URL}/api/v3/depth?symbol={symbol}&limit={limit}")
-        } else {
-            format!("{PROD_API_URL}/api/v3/depth?symbol={symbol}")
-        };
-        let resp = self.client.get(url).send().await?;
+        let params = Params::new()
+            .add("symbol", symbol)
+            .add_opt("limit", depth);
+
+        self.public_request("/api/v3/depth", params).await
+    }
+
+    /// 24 hour rolling stats for `symbol`, or every symbol when `None`.
+    pub async fn get_ticker_24hr(&self, symbol: Option<&str>) -> Result<Vec<Ticker24hr>> {
+        let params = Params::new().add_opt("symbol", symbol);
+
+        let tickers: OneOrMany<Ticker24hr> = self.public_request("/api/v3/ticker/24hr", params).await?;
+        Ok(tickers.into_vec())
+    }
+
+    /// Last price for `symbol`, or every symbol when `None`.
+    pub async fn get_price_ticker(&self, symbol: Option<&str>) -> Result<Vec<PriceTicker>> {
+        let params = Params::new().add_opt("symbol", symbol);
+
+        let tickers: OneOrMany<PriceTicker> = self.public_request("/api/v3/ticker/price", params).await?;
+        Ok(tickers.into_vec())
+    }
+
+    /// Best bid and ask for `symbol`, or every symbol when `None`. Cheaper than `get_spot_orderbook` for top of book.
+    pub async fn get_book_ticker(&self, symbol: Option<&str>) -> Result<Vec<BookTicker>> {
+        let params = Params::new().add_opt("symbol", symbol);
+
+        let tickers: OneOrMany<BookTicker> = self.public_request("/api/v3/ticker/bookTicker", params).await?;
+        Ok(tickers.into_vec())
+    }
 
-        let orderbook: Orderbook = resp.json().await?;
-        Ok(orderbook)
+    /// Current average price, `/api/v3/avgPrice` requires a symbol.
+    pub async fn get_avg_price(&self, symbol: &str) -> Result<AvgPrice> {
+        let params = Params::new().add("symbol", symbol);
+        self.public_request("/api/v3/avgPrice", params).await
     }
-}
\ No newline at end of file
+
+    /// limit: default 500; max 1000
+    pub async fn get_recent_trades(&self, symbol: &str, limit: Option<u32>) -> Result<Vec<Trade>> {
+        let params = Params::new()
+            .add("symbol", symbol)
+            .add_opt("limit", limit);
+
+        self.public_request("/api/v3/trades", params).await
+    }
+
+    /// Older trades starting at `from_id`, limit: default 500; max 1000.
+    pub async fn get_historical_trades(&self, symbol: &str, from_id: Option<u64>, limit: Option<u32>) -> Result<Vec<Trade>> {
+        let params = Params::new()
+            .add("symbol", symbol)
+            .add_opt("fromId", from_id)
+            .add_opt("limit", limit);
+
+        self.public_request("/api/v3/historicalTrades", params).await
+    }
+
+    /// Trades filled at the same time, price and side are merged. limit: default 500; max 1000.
+    pub async fn get_aggregate_trades(&self, symbol: &str, from_id: Option<u64>, start: Option<u128>, end: Option<u128>, limit: Option<u32>) -> Result<Vec<AggTrade>> {
+        let params = Params::new()
+            .add("symbol", symbol)
+            .add_opt("fromId", from_id)
+            .add_opt("startTime", start)
+            .add_opt("endTime", end)
+            .add_opt("limit", limit);
+
+        self.public_request("/api/v3/aggTrades", params).await
+    }
+
+    /// Pulls every aggregate trade between `start` and `end`, one hour window at a time,
+    /// paging within a window from the last trade time when it holds more than `AGG_TRADES_LIMIT`.
+    /// A millisecond holding a full page on its own is paged by trade id, or fails when the exchange sends no ids.
+    pub async fn get_aggregate_trades_range(&self, symbol: &str, start: u128, end: u128) -> Result<Vec<AggTrade>> {
+        let mut trades: Vec<AggTrade> = vec![];
+        let mut cursor = start;
+
+        while cursor <= end {
+            let window_end = end.min(cursor + AGG_TRADES_WINDOW_MS - 1);
+            let page = self.get_aggregate_trades(symbol, None, Some(cursor), Some(window_end), Some(AGG_TRADES_LIMIT)).await?;
+
+            match page.last().map(|t| t.time) {
+                // full page: trades at the last timestamp may continue on the next page, fetch them again from there
+                Some(last) if page.len() >= AGG_TRADES_LIMIT as usize && last > cursor => {
+                    trades.extend(page.into_iter().filter(|t| t.time < last));
+                    cursor = last;
+                },
+                Some(_) if page.len() >= AGG_TRADES_LIMIT as usize => {
+                    trades.extend(self.aggregate_trades_at(symbol, cursor, page).await?);
+                    cursor += 1;
+                },
+                _ => {
+                    trades.extend(page);
+                    cursor = window_end + 1;
+                }
+            }
+        }
+
+        Ok(trades)
+    }
+
+    /// Every aggregate trade stamped `time`, continuing from a full `page` of them with `fromId`.
+    async fn aggregate_trades_at(&self, symbol: &str, time: u128, mut page: Vec<AggTrade>) -> Result<Vec<AggTrade>> {
+        let mut trades = vec![];
+
+        loop {
+            let last = page.last().map(|t| (t.id, t.time));
+            trades.extend(page.iter().filter(|t| t.time == time).cloned());
+
+            let last_id = match last {
+                Some((id, last_time)) if page.len() >= AGG_TRADES_LIMIT as usize && last_time == time => id,
+                _ => return Ok(trades)
+            };
+
+            let Some(last_id) = last_id else {
+                return Err(MexcError::InvalidRequest(format!(
+                    "more than {AGG_TRADES_LIMIT} aggregate trades of {symbol} at {time} and no trade ids to page by"
+                )));
+            };
+
+            page = self.get_aggregate_trades(symbol, Some(last_id + 1), None, None, Some(AGG_TRADES_LIMIT)).await?;
+        }
+    }
+
+    /// Candles in ms timestamps `start`..=`end`, limit: default 500; max 1000.
+    pub async fn get_klines(&self, symbol: &str, interval: Interval, start: Option<u128>, end: Option<u128>, limit: Option<u32>) -> Result<Vec<Kline>> {
+        let params = Params::new()
+            .add("symbol", symbol)
+            .add("interval", interval)
+            .add_opt("startTime", start)
+            .add_opt("endTime", end)
+            .add_opt("limit", limit);
+
+        self.public_request("/api/v3/klines", params).await
+    }
+
+    /// Pulls every candle between `start` and `end`, paging by `KLINES_LIMIT` from the last open time.
+    pub async fn get_klines_range(&self, symbol: &str, interval: Interval, start: u128, end: u128) -> Result<Vec<Kline>> {
+        let mut klines: Vec<Kline> = vec![];
+        let mut cursor = start;
+
+        while cursor <= end {
+            let page = self.get_klines(symbol, interval, Some(cursor), Some(end), Some(KLINES_LIMIT)).await?;
+            let page_len = page.len();
+
+            let Some(last_open) = page.last().map(|k| k.open_time) else {
+                break;
+            };
+
+            klines.extend(page.into_iter().filter(|k| k.open_time >= cursor));
+
+            if page_len < KLINES_LIMIT as usize || last_open < cursor {
+                break;
+            }
+            cursor = last_open + 1;
+        }
+
+        Ok(klines)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+
+    use super::*;
+    use crate::registry::SymbolRegistry;
+    use crate::utils::number;
+
+    pub(crate) const PLS_SYMBOL: &str = r#"{"symbol":"PLSUSDT","status":"1","baseAsset":"PLS","baseAssetPrecision":2,"quoteAsset":"USDT","quotePrecision":8,"quoteAssetPrecision":8,"baseCommissionPrecision":2,"quoteCommissionPrecision":8,"orderTypes":["LIMIT","MARKET","LIMIT_MAKER","STOP_LIMIT"],"isSpotTradingAllowed":true,"isMarginTradingAllowed":false,"quoteAmountPrecision":"1.000000000000000000000000000000","baseSizePrecision":"0","permissions":["SPOT"],"filters":[{"filterType":"PERCENT_PRICE_BY_SIDE","bidMultiplierUp":"5","bidMultiplierDown":"0.2","askMultiplierUp":"5","askMultiplierDown":"0.2"}],"maxQuoteAmount":"2000000.000000000000000000000000000000","makerCommission":"0","takerCommission":"0.0005","quoteAmountPrecisionMarket":"1.000000000000000000000000000000","maxQuoteAmountMarket":"100000.000000000000000000000000000000","fullName":"PulseChain","tradeSideType":1}"#;
+
+    #[test]
+    fn test_decode_symbol_info() {
+        let info: SymbolInfo = serde_json::from_str(PLS_SYMBOL).unwrap();
+
+        assert_eq!(info.status, SymbolStatus::Online);
+        assert_eq!(info.order_types, vec![OrderType::LIMIT, OrderType::MARKET, OrderType::LIMIT_MAKER]);
+        assert!(matches!(info.filters[0], SymbolFilter::PercentPriceBySide { bid_multiplier_up, .. } if bid_multiplier_up == 5.0));
+
+        let rules = info.rules();
+        assert_eq!(rules.price_tick, number(1e-8));
+        assert_eq!(rules.quantity_step, number(0.01));
+        assert_eq!(rules.min_notional, number(1.0));
+    }
+
+    #[test]
+    fn test_symbol_registry() {
+        let pls_usdc = PLS_SYMBOL.replace("PLSUSDT", "PLSUSDC").replace(r#""quoteAsset":"USDT""#, r#""quoteAsset":"USDC""#);
+        let btc_usdt = PLS_SYMBOL.replace("PLS", "BTC").replace(r#""baseSizePrecision":"0""#, r#""baseSizePrecision":"0.0001""#);
+        let json = format!(r#"{{"serverTime":1700000000000,"symbols":[{PLS_SYMBOL},{pls_usdc},{btc_usdt}]}}"#);
+
+        let registry = SymbolRegistry::from(serde_json::from_str::<ExchangeInfo>(&json).unwrap());
+        assert_eq!(registry.timestamp, 1700000000000);
+        assert_eq!(registry.len(), 3);
+        assert_eq!(registry.get("BTCUSDT").unwrap().base_asset, "BTC");
+
+        let mut pls: Vec<_> = registry.by_base_asset("PLS").iter().map(|info| info.symbol.as_str()).collect();
+        pls.sort();
+        assert_eq!(pls, ["PLSUSDC", "PLSUSDT"]);
+        assert_eq!(registry.by_quote_asset("USDT").len(), 2);
+        assert_eq!(registry.by_quote_asset("USDC")[0].symbol, "PLSUSDC");
+        assert!(registry.by_base_asset("ETH").is_empty());
+
+        assert_eq!(registry.rules("PLSUSDT").unwrap().quantity_step, number(0.01));
+        assert_eq!(registry.rules("BTCUSDT").unwrap().quantity_step, number(0.0001));
+        assert_eq!(registry.rules("BTCUSDT").unwrap().max_notional_market, number(100000.0));
+        assert!(registry.rules("ETHUSDT").is_none());
+    }
+
+    #[test]
+    fn test_decode_klines() {
+        let json = r#"[[1640804880000,"47482.36","47482.36","47416.57","47436.1","3.550717",1640804940000,"168387.3"]]"#;
+        let klines: Vec<Kline> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(klines[0].open_time, 1640804880000);
+        assert_eq!(klines[0].low, number(47416.57));
+        assert_eq!(klines[0].close_time, 1640804940000);
+        assert_eq!(klines[0].quote_volume, number(168387.3));
+        assert_eq!(Interval::OneWeek.to_string(), "1W");
+    }
+
+    #[test]
+    fn test_decode_trades() {
+        let json = r#"[{"id":null,"price":"23","qty":"0.8","quoteQty":"18.4","time":1641380483000,"isBuyerMaker":true,"isBestMatch":true}]"#;
+        let trades: Vec<Trade> = serde_json::from_str(json).unwrap();
+        assert_eq!(trades[0].quote_quantity, number(18.4));
+        assert!(trades[0].id.is_none());
+
+        let json = r#"[{"a":null,"f":null,"l":null,"p":"46782.67","q":"0.0038","T":1641380483000,"m":false,"M":true}]"#;
+        let trades: Vec<AggTrade> = serde_json::from_str(json).unwrap();
+        assert_eq!(trades[0].price, number(46782.67));
+        assert!(!trades[0].is_buyer_maker);
+    }
+
+    #[test]
+    fn test_decode_tickers() {
+        let json = r#"{"symbol":"BTCUSDT","bidPrice":"184.34","bidQty":"3.1","askPrice":"184.35","askQty":"31.28"}"#;
+        let tickers = serde_json::from_str::<OneOrMany<BookTicker>>(json).unwrap().into_vec();
+        assert_eq!(tickers.len(), 1);
+        assert_eq!(tickers[0].ask_price, number(184.35));
+
+        let json = r#"[{"symbol":"BTCUSDT","price":"184.34"},{"symbol":"ETHUSDT","price":"3650.1"}]"#;
+        let tickers = serde_json::from_str::<OneOrMany<PriceTicker>>(json).unwrap().into_vec();
+        assert_eq!(tickers.len(), 2);
+
+        let json = r#"{"symbol":"BTCUSDT","priceChange":"184.34","priceChangePercent":"0.00400048","prevClosePrice":"46079.37","lastPrice":"46263.71","bidPrice":"46260.38","bidQty":"","askPrice":"46260.41","askQty":"","openPrice":"46079.37","highPrice":"47550.01","lowPrice":"45555.5","volume":"1732.461487","quoteVolume":null,"openTime":1641349500000,"closeTime":1641349582808,"count":null}"#;
+        let tickers = serde_json::from_str::<OneOrMany<Ticker24hr>>(json).unwrap().into_vec();
+        assert_eq!(tickers[0].last_price, number(46263.71));
+    }
+}
@@ -1,16 +1,25 @@
-use crate::{Mexc, PROD_API_URL, utils::{parse_string_to_f64, get_timestamp, serialize_f64_as_string}};
-use anyhow::{anyhow, bail};
-use reqwest::{StatusCode, Response};
+use crate::{Mexc, MexcError, error::Result, error_codes::ErrorCode, request::{Params, collect_windows}, utils::{Number, format_number, parse_string_to_number, serialize_number_as_string, serialize_optional_number}};
+use reqwest::{Method, Response};
 use serde::{Deserialize, Serialize};
+use serde::de::IgnoredAny;
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
 
 pub const DEFAULT_RECV_WINDOW: u64 = 5000;
 
+/// Widest `startTime`..`endTime` span accepted by `/api/v3/allOrders` and `/api/v3/myTrades`.
+pub const HISTORY_WINDOW_MS: u128 = 7 * 24 * 3600 * 1000;
+
+/// Max orders per `/api/v3/allOrders` request.
+pub const ALL_ORDERS_LIMIT: u32 = 1000;
+
+/// Max fills per `/api/v3/myTrades` request.
+pub const MY_TRADES_LIMIT: u32 = 100;
+
 
 
 #[allow(non_camel_case_types)]
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum OrderType {
     LIMIT,
     MARKET,
@@ -30,7 +39,7 @@ impl std::fmt::Display for OrderType {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
 pub enum OrderSide {
     BUY,
     SELL
@@ -45,7 +54,7 @@ impl std::fmt::Display for OrderSide {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum OrderStatus {
     NEW,
     FILLED,
@@ -54,6 +63,48 @@ pub enum OrderStatus {
     PARTIALLY_CANCELED
 }
 
+impl OrderStatus {
+    /// True once the order can no longer fill.
+    pub fn is_final(&self) -> bool {
+        matches!(self, OrderStatus::FILLED | OrderStatus::CANCELED | OrderStatus::PARTIALLY_CANCELED)
+    }
+}
+
+/// Addresses an order either by the exchange `orderId` or by the client id it was submitted with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderRef {
+    OrderId(String),
+    ClientOrderId(String)
+}
+
+impl OrderRef {
+    pub(crate) fn add_to(&self, params: Params) -> Params {
+        match self {
+            OrderRef::OrderId(id) => params.add("orderId", id),
+            OrderRef::ClientOrderId(id) => params.add("origClientOrderId", id)
+        }
+    }
+}
+
+/// Plain strings are exchange order ids.
+impl From<&str> for OrderRef {
+    fn from(order_id: &str) -> Self {
+        OrderRef::OrderId(order_id.to_string())
+    }
+}
+
+impl From<String> for OrderRef {
+    fn from(order_id: String) -> Self {
+        OrderRef::OrderId(order_id)
+    }
+}
+
+impl From<&String> for OrderRef {
+    fn from(order_id: &String) -> Self {
+        OrderRef::OrderId(order_id.clone())
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 pub struct OrderReceipt {
@@ -62,53 +113,212 @@ pub struct OrderReceipt {
     pub order_id: String,
     #[serde(rename = "orderListId")]
     pub order_list_id: i64,
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub price: f64,
-    #[serde(rename = "origQty", deserialize_with = "parse_string_to_f64")]
-    pub orig_qty: f64,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub price: Number,
+    #[serde(rename = "origQty", deserialize_with = "parse_string_to_number")]
+    pub orig_qty: Number,
     #[serde(rename = "type")]
     pub order_type: OrderType,
     pub side: OrderSide,
     #[serde(rename = "transactTime")]
     pub transact_time: u128,
+    /// Not echoed by MEXC, filled in from the submitted order
+    #[serde(rename = "clientOrderId", default)]
+    pub client_order_id: Option<String>,
 }
 
 #[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 pub struct CancelledOrder {
     pub symbol: String,
-    //#[serde(rename = "origClientOrderId")]
-    //pub orig_client_order_id: String,
+    #[serde(rename = "origClientOrderId", default)]
+    pub orig_client_order_id: Option<String>,
     #[serde(rename = "orderId")]
     pub order_id: String,
-    //#[serde(rename = "clientOrderId")]
-    //pub client_order_id: String,
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub price: f64,
-    #[serde(rename = "origQty", deserialize_with = "parse_string_to_f64")]
-    pub orig_qty: f64,
-    #[serde(rename = "executedQty", deserialize_with = "parse_string_to_f64")]
-    pub exec_qty: f64,
-    #[serde(rename = "cummulativeQuoteQty", deserialize_with = "parse_string_to_f64")]
-    pub cum_quote_qty: f64,
+    #[serde(rename = "clientOrderId", default)]
+    pub client_order_id: Option<String>,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub price: Number,
+    #[serde(rename = "origQty", deserialize_with = "parse_string_to_number")]
+    pub orig_qty: Number,
+    #[serde(rename = "executedQty", deserialize_with = "parse_string_to_number")]
+    pub exec_qty: Number,
+    #[serde(rename = "cummulativeQuoteQty", deserialize_with = "parse_string_to_number")]
+    pub cum_quote_qty: Number,
     //#[serde(rename = "timeInForce")]
     //pub time_in_force: String,
     #[serde(rename = "type")]
     pub order_type: OrderType,
     pub side: OrderSide,
+    pub status: Option<OrderStatus>,
 }
 
 
 #[derive(Serialize,Deserialize, Debug)]
 pub struct Order {
     pub symbol: String,
-    #[serde(serialize_with = "serialize_f64_as_string")]
-    pub price: f64,
-    #[serde(serialize_with = "serialize_f64_as_string")]
-    pub quantity: f64,
+    #[serde(serialize_with = "serialize_number_as_string", deserialize_with = "parse_string_to_number")]
+    pub price: Number,
+    #[serde(serialize_with = "serialize_number_as_string", deserialize_with = "parse_string_to_number")]
+    pub quantity: Number,
     pub side: OrderSide,
     #[serde(rename = "type")]
-    pub order_type: OrderType
+    pub order_type: OrderType,
+    #[serde(rename = "newClientOrderId", default, skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>
+}
+
+/// A spot order as sent to `submit_order` or `batch_orders`. Each constructor only takes the
+/// parameters its order type allows, e.g. a `market_quote` order has a `quoteOrderQty` and no price.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct OrderRequest {
+    pub(crate) symbol: String,
+    side: OrderSide,
+    #[serde(rename = "type")]
+    pub(crate) order_type: OrderType,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_optional_number")]
+    pub(crate) price: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_optional_number")]
+    pub(crate) quantity: Option<Number>,
+    #[serde(rename = "quoteOrderQty", skip_serializing_if = "Option::is_none", serialize_with = "serialize_optional_number")]
+    pub(crate) quote_order_qty: Option<Number>,
+    #[serde(rename = "newClientOrderId", skip_serializing_if = "Option::is_none")]
+    client_order_id: Option<String>
+}
+
+impl OrderRequest {
+
+    fn priced(symbol: &str, side: OrderSide, order_type: OrderType, price: Number, quantity: Number) -> Self {
+        OrderRequest {
+            symbol: symbol.to_string(),
+            side,
+            order_type,
+            price: Some(price),
+            quantity: Some(quantity),
+            quote_order_qty: None,
+            client_order_id: None
+        }
+    }
+
+    pub fn limit(symbol: &str, side: OrderSide, price: Number, quantity: Number) -> Self {
+        Self::priced(symbol, side, OrderType::LIMIT, price, quantity)
+    }
+
+    /// Post only, rejected instead of taking liquidity.
+    pub fn limit_maker(symbol: &str, side: OrderSide, price: Number, quantity: Number) -> Self {
+        Self::priced(symbol, side, OrderType::LIMIT_MAKER, price, quantity)
+    }
+
+    /// Fills what it can at `price` or better and cancels the rest.
+    pub fn immediate_or_cancel(symbol: &str, side: OrderSide, price: Number, quantity: Number) -> Self {
+        Self::priced(symbol, side, OrderType::IMMEDIATE_OR_CANCEL, price, quantity)
+    }
+
+    /// Fills completely at `price` or better, or not at all.
+    pub fn fill_or_kill(symbol: &str, side: OrderSide, price: Number, quantity: Number) -> Self {
+        Self::priced(symbol, side, OrderType::FILL_OR_KILL, price, quantity)
+    }
+
+    /// Market order for `quantity` of the base asset.
+    pub fn market(symbol: &str, side: OrderSide, quantity: Number) -> Self {
+        OrderRequest {
+            symbol: symbol.to_string(),
+            side,
+            order_type: OrderType::MARKET,
+            price: None,
+            quantity: Some(quantity),
+            quote_order_qty: None,
+            client_order_id: None
+        }
+    }
+
+    /// Market order spending (or, when selling, receiving) `quote_order_qty` of the quote asset.
+    pub fn market_quote(symbol: &str, side: OrderSide, quote_order_qty: Number) -> Self {
+        OrderRequest {
+            symbol: symbol.to_string(),
+            side,
+            order_type: OrderType::MARKET,
+            price: None,
+            quantity: None,
+            quote_order_qty: Some(quote_order_qty),
+            client_order_id: None
+        }
+    }
+
+    /// Sends `newClientOrderId`, so the order can be queried or cancelled by it and `submit_order` can
+    /// check whether a failed submit was placed before resending it.
+    pub fn with_client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn side(&self) -> OrderSide {
+        self.side
+    }
+
+    pub fn order_type(&self) -> OrderType {
+        self.order_type
+    }
+
+    pub fn price(&self) -> Option<Number> {
+        self.price
+    }
+
+    pub fn quantity(&self) -> Option<Number> {
+        self.quantity
+    }
+
+    pub fn quote_order_qty(&self) -> Option<Number> {
+        self.quote_order_qty
+    }
+
+    pub fn client_order_id(&self) -> Option<&str> {
+        self.client_order_id.as_deref()
+    }
+
+    pub(crate) fn params(&self) -> Params {
+        Params::new()
+            .add("symbol", &self.symbol)
+            .add("side", self.side)
+            .add("type", self.order_type)
+            .add_opt("quantity", self.quantity.map(format_number))
+            .add_opt("quoteOrderQty", self.quote_order_qty.map(format_number))
+            .add_opt("price", self.price.map(format_number))
+            .add_opt("newClientOrderId", self.client_order_id.as_deref())
+    }
+}
+
+/// A MARKET `Order` goes by base quantity and drops its price.
+impl From<&Order> for OrderRequest {
+    fn from(order: &Order) -> Self {
+        let price = if order.order_type == OrderType::MARKET { None } else { Some(order.price) };
+
+        OrderRequest {
+            symbol: order.symbol.clone(),
+            side: order.side,
+            order_type: order.order_type,
+            price,
+            quantity: Some(order.quantity),
+            quote_order_qty: None,
+            client_order_id: order.client_order_id.clone()
+        }
+    }
+}
+
+impl From<Order> for OrderRequest {
+    fn from(order: Order) -> Self {
+        Self::from(&order)
+    }
+}
+
+impl From<&OrderRequest> for OrderRequest {
+    fn from(order: &OrderRequest) -> Self {
+        order.clone()
+    }
 }
 
 #[allow(dead_code)]
@@ -118,14 +328,22 @@ pub struct OrderQuery {
     #[serde(rename = "orderId")]
     pub order_id: String,
 
-    #[serde(deserialize_with = "parse_string_to_f64")]
-    pub price: f64,
+    #[serde(rename = "clientOrderId", default)]
+    pub client_order_id: Option<String>,
+
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub price: Number,
+
+    #[serde(rename = "origQty", deserialize_with = "parse_string_to_number")]
+    pub orig_qty: Number,
 
-    #[serde(rename = "origQty", deserialize_with = "parse_string_to_f64")]
-    pub orig_qty: f64,
+    #[serde(rename = "executedQty", deserialize_with = "parse_string_to_number")]
+    pub exec_qty: Number,
 
-    #[serde(rename = "executedQty", deserialize_with = "parse_string_to_f64")]
-    pub exec_qty: f64,
+    #[serde(rename = "cummulativeQuoteQty", default, deserialize_with = "parse_string_to_number")]
+    pub cum_quote_qty: Number,
+
+    pub status: OrderStatus,
 
     #[serde(rename = "type")]
     pub order_type: OrderType,
@@ -138,11 +356,52 @@ pub struct OrderQuery {
     pub last_update: Option<u128>,
 }
 
+impl From<OrderQuery> for OrderReceipt {
+    fn from(order: OrderQuery) -> Self {
+        Self {
+            symbol: order.symbol,
+            order_id: order.order_id,
+            order_list_id: -1,
+            price: order.price,
+            orig_qty: order.orig_qty,
+            order_type: order.order_type,
+            side: order.side,
+            transact_time: order.created_time,
+            client_order_id: order.client_order_id
+        }
+    }
+}
+
+/// One of our fills from `/api/v3/myTrades`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountTrade {
+    pub symbol: String,
+    pub id: String,
+    #[serde(rename = "orderId")]
+    pub order_id: String,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub price: Number,
+    #[serde(rename = "qty", deserialize_with = "parse_string_to_number")]
+    pub quantity: Number,
+    #[serde(rename = "quoteQty", deserialize_with = "parse_string_to_number")]
+    pub quote_quantity: Number,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub commission: Number,
+    #[serde(rename = "commissionAsset")]
+    pub commission_asset: String,
+    pub time: u128,
+    #[serde(rename = "isBuyer")]
+    pub is_buyer: bool,
+    #[serde(rename = "isMaker")]
+    pub is_maker: bool
+}
+
 impl Mexc {
 
-    pub fn sign_request(&self, order_details: String) -> anyhow::Result<String> {
-        let secret_key = self.api_secret.as_ref().ok_or_else(|| anyhow!("Missing secret key"))?;
-        let mut signed_key = Hmac::<Sha256>::new_from_slice(secret_key.as_bytes())?;
+    pub fn sign_request(&self, order_details: String) -> Result<String> {
+        let secret_key = self.api_secret.as_ref().ok_or(MexcError::MissingCredentials("secret key"))?;
+        let mut signed_key = Hmac::<Sha256>::new_from_slice(secret_key.as_bytes())
+            .expect("HMAC can take key of any size");
         signed_key.update(order_details.as_bytes());
         let signature = hex::encode(signed_key.finalize().into_bytes());
         let signed_order_details: String = format!("{}&signature={}", order_details, signature);
@@ -150,8 +409,8 @@ impl Mexc {
     }
 
     /*
-    pub fn get_blank_signature(&self) -> anyhow::Result<String> {
-        let secret_key = self.api_secret.as_ref().ok_or_else(|| anyhow!("Missing secret key"))?;
+    pub fn get_blank_signature(&self) -> Result<String> {
+        let secret_key = self.api_secret.as_ref().ok_or(MexcError::MissingCredentials("secret key"))?;
         let signed_key = Hmac::<Sha256>::new_from_slice(secret_key.as_bytes())?;
         let signature = hex::encode(signed_key.finalize().into_bytes());
         let signed_order_details: String = format!("signature={}", signature);
@@ -159,146 +418,183 @@ impl Mexc {
     }
     */
     
-    pub async fn post_signed(&self, url: &str) -> anyhow::Result<Response> {
-        let api_key = self.api_key.as_ref().ok_or_else(|| anyhow!("Missing api key"))?;
+    pub async fn post_signed(&self, url: &str) -> Result<Response> {
+        let api_key = self.api_key.as_ref().ok_or(MexcError::MissingCredentials("api key"))?;
 
-        let resp = self.client
+        let request = self.client
         .post(url)
-        .header("X-MEXC-APIKEY", api_key)
-        .send().await?;
+        .header("X-MEXC-APIKEY", api_key);
+        let resp = self.send(request).await?;
         Ok(resp)
     }
 
-    pub async fn put_signed(&self, url: &str) -> anyhow::Result<Response> {
-        let api_key = self.api_key.as_ref().ok_or_else(|| anyhow!("Missing api key"))?;
+    pub async fn put_signed(&self, url: &str) -> Result<Response> {
+        let api_key = self.api_key.as_ref().ok_or(MexcError::MissingCredentials("api key"))?;
 
-        let resp = self.client
+        let request = self.client
         .put(url)
-        .header("X-MEXC-APIKEY", api_key)
-        .send().await?;
+        .header("X-MEXC-APIKEY", api_key);
+        let resp = self.send(request).await?;
         Ok(resp)
     }
 
-    pub async fn delete_signed(&self, url: &str) -> anyhow::Result<Response> {
-        let api_key = self.api_key.as_ref().ok_or_else(|| anyhow!("Missing api key"))?;
+    pub async fn delete_signed(&self, url: &str) -> Result<Response> {
+        let api_key = self.api_key.as_ref().ok_or(MexcError::MissingCredentials("api key"))?;
         
-        let resp = self.client
+        let request = self.client
         .delete(url)
-        .header("X-MEXC-APIKEY", api_key)
-        .send().await?;
+        .header("X-MEXC-APIKEY", api_key);
+        let resp = self.send(request).await?;
         Ok(resp)
     }
 
-    pub async fn submit_order(&self, symbol: &str, side: OrderSide, order_type: OrderType, price: f64, quantity: f64, recv_window: Option<u64>) -> anyhow::Result<OrderReceipt> {
-        let recv_window = recv_window.unwrap_or(DEFAULT_RECV_WINDOW);
-        let timestamp = get_timestamp();
+    /// A retryable failure is only resent for orders with a `client_order_id`, and only once `query_order`
+    /// finds no order under that id, so a lost response never places the order twice. Found orders are
+    /// returned as the receipt.
+    pub async fn submit_order(&self, order: impl Into<OrderRequest>, recv_window: Option<u64>) -> Result<OrderReceipt> {
+        let order = order.into();
+        let mut attempt = 0;
+
+        loop {
+            let params = order.params().recv_window(recv_window);
+
+            let err = match self.signed_request::<OrderReceipt>(Method::POST, "/api/v3/order", params).await {
+                Ok(mut receipt) => {
+                    if receipt.client_order_id.is_none() {
+                        receipt.client_order_id = order.client_order_id.clone();
+                    }
+                    return Ok(receipt);
+                },
+                Err(err) => err
+            };
+
+            let Some(client_order_id) = &order.client_order_id else {
+                return Err(err);
+            };
+
+            if !err.is_retryable() || attempt >= self.retry_policy.max_retries {
+                return Err(err);
+            }
 
-        let order_request = format!("symbol={symbol}&side={side}&type={order_type}&quantity={quantity}&price={price}&recvWindow={recv_window}&timestamp={timestamp}");
-        let signed_order = self.sign_request(order_request)?;
-        let url = format!("{PROD_API_URL}/api/v3/order?{signed_order}");
-        let resp: Response = self.post_signed(&url).await?;
+            tokio::time::sleep(self.retry_policy.backoff(attempt)).await;
 
-        if resp.status() == StatusCode::OK {
-            let receipe: OrderReceipt = resp.json().await?;
-            Ok(receipe)
-        } else {
-            let err = resp.text().await?;
-            bail!(err);
+            match self.query_order(&order.symbol, OrderRef::ClientOrderId(client_order_id.clone()), recv_window).await {
+                Ok(placed) => return Ok(placed.into()),
+                Err(lookup) if lookup.error_code() == Some(ErrorCode::UnknownOrder) => attempt += 1,
+                Err(_) => return Err(err)
+            }
         }
     }
 
-    pub async fn batch_orders(&self, orders: Vec<Order>, recv_window: Option<u64>) -> anyhow::Result<Vec<OrderReceipt>> {
+    /// Dry run of `submit_order`: same parameters and signature against `/api/v3/order/test`,
+    /// so the exchange validates the order without placing it.
+    pub async fn test_order(&self, order: impl Into<OrderRequest>, recv_window: Option<u64>) -> Result<()> {
+        let order = order.into();
+        let params = order.params().recv_window(recv_window);
+
+        let _: IgnoredAny = self.signed_request(Method::POST, "/api/v3/order/test", params).await?;
+        Ok(())
+    }
+
+    /// Up to 20 orders of one symbol in a single request.
+    pub async fn batch_orders(&self, orders: Vec<impl Into<OrderRequest>>, recv_window: Option<u64>) -> Result<Vec<OrderReceipt>> {
         if orders.is_empty() {
-            bail!("No orders in vector");
+            return Err(MexcError::InvalidRequest("No orders in vector".into()));
         }
 
-        let recv_window = recv_window.unwrap_or(DEFAULT_RECV_WINDOW);
-        let timestamp = get_timestamp();
-
-        let json = serde_json::to_string(&orders)?;
+        let orders: Vec<OrderRequest> = orders.into_iter().map(Into::into).collect();
 
-        let encoded_orders = url::form_urlencoded::Serializer::new(String::new())
-        .append_pair("batchOrders", &json)
-        .finish();
+        let json = serde_json::to_string(&orders).map_err(|err| MexcError::InvalidRequest(err.to_string()))?;
 
-        let order_request = format!("{encoded_orders}&recvWindow={recv_window}&timestamp={timestamp}");
+        let params = Params::new()
+            .add("batchOrders", json)
+            .recv_window(recv_window);
 
-        let signed_order = self.sign_request(order_request)?;
-        let url = format!("{PROD_API_URL}/api/v3/batchOrders?{signed_order}");
+        let mut receipts: Vec<OrderReceipt> = self.signed_request(Method::POST, "/api/v3/batchOrders", params).await?;
 
-        let resp: Response = self.post_signed(&url).await?;
+        // receipts come back in submission order
+        for (receipt, order) in receipts.iter_mut().zip(orders) {
+            if receipt.client_order_id.is_none() {
+                receipt.client_order_id = order.client_order_id;
+            }
+        }
+        Ok(receipts)
+    }
 
-        if resp.status() == StatusCode::OK {
+    pub async fn cancel_all_orders(&self, symbol: &str, recv_window: Option<u64>) -> Result<Vec<CancelledOrder>> {
+        let params = Params::new()
+            .add("symbol", symbol)
+            .recv_window(recv_window);
 
+        self.signed_request(Method::DELETE, "/api/v3/openOrders", params).await
+    }
 
-            let txt = resp.text().await?;
-        
-            let res: Result<Vec<OrderReceipt>, _> = serde_json::from_str(&txt);
+    /// Cancels by exchange id, or by client id with `OrderRef::ClientOrderId`.
+    pub async fn cancel_order(&self, symbol: &str, order: impl Into<OrderRef>, recv_window: Option<u64>) -> Result<CancelledOrder> {
+        let params = order.into().add_to(Params::new().add("symbol", symbol))
+            .recv_window(recv_window);
 
-            match res {
-                Ok(receipts) => Ok(receipts),
-                Err(err) => {
-                    bail!("Err: {err} on {txt}");
-                }
-            }
-        } else {
-            let err = resp.text().await?;
-            bail!(err);
-        }
+        self.signed_request(Method::DELETE, "/api/v3/order", params).await
     }
 
-    pub async fn cancel_all_orders(&self, symbol: &str, recv_window: Option<u64>) -> anyhow::Result<Vec<CancelledOrder>> {
-        let recv_window = recv_window.unwrap_or(DEFAULT_RECV_WINDOW);
-        let timestamp = get_timestamp();
+    pub async fn get_open_orders(&self, symbol: &str,recv_window: Option<u64>) -> Result<Vec<OrderQuery>> {
+        let params = Params::new()
+            .add("symbol", symbol)
+            .recv_window(recv_window);
 
-        let order_request = format!("symbol={symbol}&recvWindow={recv_window}&timestamp={timestamp}");
-        let signed_order = self.sign_request(order_request)?;
-        let url = format!("{PROD_API_URL}/api/v3/openOrders?{signed_order}");
-        let resp: Response = self.delete_signed(&url).await?;
-
-        if resp.status() == StatusCode::OK {
-            let cancelled_orders: Vec<CancelledOrder> = resp.json().await?;
-            Ok(cancelled_orders)
-        } else {
-            let err = resp.text().await?;
-            bail!(err);
-        }
+        self.signed_request(Method::GET, "/api/v3/openOrders", params).await
     }
 
-    pub async fn cancel_order(&self, symbol: &str, order_id: &str,recv_window: Option<u64>) -> anyhow::Result<CancelledOrder> {
-        let recv_window = recv_window.unwrap_or(DEFAULT_RECV_WINDOW);
-        let timestamp = get_timestamp();
+    pub async fn query_order(&self, symbol: &str, order: impl Into<OrderRef>, recv_window: Option<u64>) -> Result<OrderQuery> {
+        let params = order.into().add_to(Params::new().add("symbol", symbol))
+            .recv_window(recv_window);
 
-        let order_request = format!("symbol={symbol}&orderId={order_id}&recvWindow={recv_window}&timestamp={timestamp}");
-        let signed_order = self.sign_request(order_request)?;
-        let url = format!("{PROD_API_URL}/api/v3/order?{signed_order}");
-        let resp: Response = self.delete_signed(&url).await?;
+        self.signed_request(Method::GET, "/api/v3/order", params).await
+    }
 
-        if resp.status() == StatusCode::OK {
-            let cancelled_order: CancelledOrder = resp.json().await?;
-            Ok(cancelled_order)
-        } else {
-            let err = resp.text().await?;
-            bail!(err);
-        }
+    /// Orders of every status in ms timestamps `start`..=`end`, at most 7 days apart.
+    /// MEXC defaults to the last 24 hours. limit: default 500; max 1000
+    pub async fn get_all_orders(&self, symbol: &str, start: Option<u128>, end: Option<u128>, limit: Option<u32>, recv_window: Option<u64>) -> Result<Vec<OrderQuery>> {
+        let params = Params::new()
+            .add("symbol", symbol)
+            .add_opt("startTime", start)
+            .add_opt("endTime", end)
+            .add_opt("limit", limit)
+            .recv_window(recv_window);
+
+        self.signed_request(Method::GET, "/api/v3/allOrders", params).await
     }
 
-    pub async fn get_open_orders(&self, symbol: &str,recv_window: Option<u64>) -> anyhow::Result<Vec<OrderQuery>> {
+    /// Every order between `start` and `end`, split into requests small enough for the 7 day window and page limit.
+    pub async fn get_all_orders_range(&self, symbol: &str, start: u128, end: u128, recv_window: Option<u64>) -> Result<Vec<OrderQuery>> {
+        let mut orders = collect_windows(start, end, HISTORY_WINDOW_MS, ALL_ORDERS_LIMIT as usize, |from, to| {
+            self.get_all_orders(symbol, Some(from), Some(to), Some(ALL_ORDERS_LIMIT), recv_window)
+        }).await?;
+
+        orders.sort_by_key(|o| o.created_time);
+        Ok(orders)
+    }
 
-        let recv_window = recv_window.unwrap_or(DEFAULT_RECV_WINDOW);
-        let timestamp = get_timestamp();
+    /// Our fills in ms timestamps `start`..=`end`, optionally for one order. limit: default 100; max 100
+    pub async fn get_my_trades(&self, symbol: &str, order_id: Option<&str>, start: Option<u128>, end: Option<u128>, limit: Option<u32>, recv_window: Option<u64>) -> Result<Vec<AccountTrade>> {
+        let params = Params::new()
+            .add("symbol", symbol)
+            .add_opt("orderId", order_id)
+            .add_opt("startTime", start)
+            .add_opt("endTime", end)
+            .add_opt("limit", limit)
+            .recv_window(recv_window);
+
+        self.signed_request(Method::GET, "/api/v3/myTrades", params).await
+    }
 
-        let order_request = format!("symbol={symbol}&recvWindow={recv_window}&timestamp={timestamp}");
-        let signed_order = self.sign_request(order_request)?;
-        let url = format!("{PROD_API_URL}/api/v3/openOrders?{signed_order}");
-        let resp: Response = self.get_signed(&url).await?;
+    /// Every fill between `start` and `end`, split into requests small enough for the 7 day window and page limit.
+    pub async fn get_my_trades_range(&self, symbol: &str, start: u128, end: u128, recv_window: Option<u64>) -> Result<Vec<AccountTrade>> {
+        let mut trades = collect_windows(start, end, HISTORY_WINDOW_MS, MY_TRADES_LIMIT as usize, |from, to| {
+            self.get_my_trades(symbol, None, Some(from), Some(to), Some(MY_TRADES_LIMIT), recv_window)
+        }).await?;
 
-        if resp.status() == StatusCode::OK {
-            let orders: Vec<OrderQuery> = resp.json().await?;
-            Ok(orders)
-        } else {
-            let err = resp.text().await?;
-            bail!(err);
-        }
+        trades.sort_by_key(|t| t.time);
+        Ok(trades)
     }
-}
\ No newline at end of file
+}
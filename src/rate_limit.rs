@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use futures::future::BoxFuture;
+
+// https://mexcdevelop.github.io/apidocs/spot_v3_en/#limits
+// https://mexcdevelop.github.io/apidocs/contract_v1_en/#access-restriction
+
+/// Bucket a request is charged against.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LimitScope {
+    /// Spot IP weight, shared by every spot request from this IP
+    SpotIp,
+    /// Spot UID weight, charged by signed trading requests
+    SpotUid,
+    /// Futures limits apply per endpoint path
+    Futures(String)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestWeight {
+    pub scope: LimitScope,
+    pub weight: u32
+}
+
+impl RequestWeight {
+    pub fn new(scope: LimitScope, weight: u32) -> Self {
+        Self { scope, weight }
+    }
+}
+
+/// Spot IP weight per endpoint path. The ticker endpoints cost more when called without a `symbol`,
+/// as they then return every symbol.
+pub fn spot_ip_weight(path: &str, has_symbol: bool) -> u32 {
+    match path {
+        "/api/v3/ticker/24hr" if !has_symbol => 40,
+        "/api/v3/ticker/price" | "/api/v3/ticker/bookTicker" if !has_symbol => 2,
+        "/api/v3/exchangeInfo" | "/api/v3/account" | "/api/v3/allOrders" | "/api/v3/myTrades" => 10,
+        "/api/v3/trades" | "/api/v3/historicalTrades" => 5,
+        "/api/v3/openOrders" => 3,
+        _ => 1
+    }
+}
+
+/// Spot paths limited by UID in addition to IP.
+pub fn is_spot_uid_limited(path: &str) -> bool {
+    matches!(path, "/api/v3/order" | "/api/v3/batchOrders" | "/api/v3/openOrders" | "/api/v3/order/test")
+}
+
+pub fn spot_request_weights(path: &str, has_symbol: bool, signed: bool) -> Vec<RequestWeight> {
+    let mut weights = vec![RequestWeight::new(LimitScope::SpotIp, spot_ip_weight(path, has_symbol))];
+
+    if signed && is_spot_uid_limited(path) {
+        weights.push(RequestWeight::new(LimitScope::SpotUid, 1));
+    }
+    weights
+}
+
+/// Futures paths carrying an id or currency in the last segment share the limit of their route.
+pub fn futures_route(path: &str) -> &str {
+    const DYNAMIC_ROUTES: [&str; 4] = [
+        "/api/v1/private/account/asset/",
+        "/api/v1/private/order/get/",
+        "/api/v1/contract/index_price/",
+        "/api/v1/contract/fair_price/"
+    ];
+
+    DYNAMIC_ROUTES.iter()
+        .find(|route| path.starts_with(*route))
+        .map(|route| route.trim_end_matches('/'))
+        .unwrap_or(path)
+}
+
+pub fn futures_request_weights(path: &str) -> Vec<RequestWeight> {
+    vec![RequestWeight::new(LimitScope::Futures(futures_route(path).to_string()), 1)]
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScopeStats {
+    pub requests: u64,
+    pub weight: u64,
+    /// Requests that had to wait for tokens
+    pub throttled: u64,
+    pub total_wait: Duration,
+    pub available: f64
+}
+
+/// Totals count each `acquire` once however many scopes it touched, `by_scope` has the per-bucket numbers.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitStats {
+    pub requests: u64,
+    /// Weight charged across all scopes
+    pub weight: u64,
+    /// Requests that had to wait on any scope
+    pub throttled: u64,
+    pub total_wait: Duration,
+    pub by_scope: HashMap<LimitScope, ScopeStats>
+}
+
+/// Throttles requests before they are sent. Implementations wait rather than fail.
+pub trait RateLimiter: Send + Sync {
+    fn acquire<'a>(&'a self, weights: &'a [RequestWeight]) -> BoxFuture<'a, ()>;
+
+    fn stats(&self) -> RateLimitStats;
+}
+
+/// Allows `capacity` weight per `period`, refilled continuously.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketConfig {
+    pub capacity: u32,
+    pub period: Duration
+}
+
+impl BucketConfig {
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        Self { capacity, period }
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        self.capacity as f64 / self.period.as_secs_f64()
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    config: BucketConfig,
+    tokens: f64,
+    last_refill: Instant,
+    stats: ScopeStats
+}
+
+impl Bucket {
+    fn new(config: BucketConfig) -> Self {
+        Self {
+            config,
+            tokens: config.capacity as f64,
+            last_refill: Instant::now(),
+            stats: ScopeStats::default()
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.config.refill_per_sec()).min(self.config.capacity as f64);
+        self.last_refill = now;
+    }
+
+    /// Takes the tokens or returns how long until enough have been refilled.
+    fn try_take(&mut self, weight: u32) -> Option<Duration> {
+        self.refill();
+
+        // A request heavier than the bucket would never fit, let it drain the full bucket instead
+        let weight = weight.min(self.config.capacity) as f64;
+
+        if self.tokens >= weight {
+            self.tokens -= weight;
+            None
+        } else {
+            Some(Duration::from_secs_f64((weight - self.tokens) / self.config.refill_per_sec()))
+        }
+    }
+}
+
+/// Default limiter with one token bucket per `LimitScope`.
+#[derive(Debug)]
+pub struct TokenBucketLimiter {
+    spot_ip: BucketConfig,
+    spot_uid: BucketConfig,
+    futures: BucketConfig,
+    buckets: Mutex<HashMap<LimitScope, Bucket>>,
+    totals: Mutex<RateLimitStats>
+}
+
+impl Default for TokenBucketLimiter {
+    fn default() -> Self {
+        Self::new(
+            BucketConfig::new(500, Duration::from_secs(10)),
+            BucketConfig::new(500, Duration::from_secs(10)),
+            BucketConfig::new(20, Duration::from_secs(2))
+        )
+    }
+}
+
+impl TokenBucketLimiter {
+
+    pub fn new(spot_ip: BucketConfig, spot_uid: BucketConfig, futures: BucketConfig) -> Self {
+        Self {
+            spot_ip,
+            spot_uid,
+            futures,
+            buckets: Mutex::new(HashMap::new()),
+            totals: Mutex::new(RateLimitStats::default())
+        }
+    }
+
+    fn config(&self, scope: &LimitScope) -> BucketConfig {
+        match scope {
+            LimitScope::SpotIp => self.spot_ip,
+            LimitScope::SpotUid => self.spot_uid,
+            LimitScope::Futures(_) => self.futures
+        }
+    }
+
+    /// Waits for the tokens of one scope, returns how long it waited.
+    async fn acquire_one(&self, weight: &RequestWeight) -> Duration {
+        let mut waited = Duration::ZERO;
+
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let bucket = buckets
+                    .entry(weight.scope.clone())
+                    .or_insert_with(|| Bucket::new(self.config(&weight.scope)));
+
+                let wait = bucket.try_take(weight.weight);
+
+                if wait.is_none() {
+                    bucket.stats.requests += 1;
+                    bucket.stats.weight += weight.weight as u64;
+                    if !waited.is_zero() {
+                        bucket.stats.throttled += 1;
+                        bucket.stats.total_wait += waited;
+                    }
+                }
+                wait
+            };
+
+            match wait {
+                Some(wait) => {
+                    tokio::time::sleep(wait).await;
+                    waited += wait;
+                },
+                None => return waited
+            }
+        }
+    }
+}
+
+impl RateLimiter for TokenBucketLimiter {
+
+    fn acquire<'a>(&'a self, weights: &'a [RequestWeight]) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let mut waited = Duration::ZERO;
+            for weight in weights {
+                waited += self.acquire_one(weight).await;
+            }
+
+            let mut totals = self.totals.lock().unwrap();
+            totals.requests += 1;
+            totals.weight += weights.iter().map(|weight| weight.weight as u64).sum::<u64>();
+            if !waited.is_zero() {
+                totals.throttled += 1;
+                totals.total_wait += waited;
+            }
+        })
+    }
+
+    fn stats(&self) -> RateLimitStats {
+        let mut buckets = self.buckets.lock().unwrap();
+        let mut stats = self.totals.lock().unwrap().clone();
+
+        for (scope, bucket) in buckets.iter_mut() {
+            bucket.refill();
+            stats.by_scope.insert(scope.clone(), ScopeStats { available: bucket.tokens, ..bucket.stats });
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_token_bucket_waits() {
+        let limiter = TokenBucketLimiter::new(
+            BucketConfig::new(2, Duration::from_millis(100)),
+            BucketConfig::new(2, Duration::from_millis(100)),
+            BucketConfig::new(2, Duration::from_millis(100))
+        );
+
+        let weights = spot_request_weights("/api/v3/order", true, true);
+        let inst = Instant::now();
+        for _ in 0..4 {
+            limiter.acquire(&weights).await;
+        }
+        assert!(inst.elapsed() >= Duration::from_millis(80));
+
+        let stats = limiter.stats();
+        assert_eq!(stats.requests, 4);
+        assert_eq!(stats.weight, 8);
+        assert_eq!(stats.by_scope[&LimitScope::SpotIp].requests, 4);
+        assert_eq!(stats.by_scope[&LimitScope::SpotUid].requests, 4);
+        assert!(stats.throttled > 0);
+    }
+
+    #[test]
+    fn test_spot_ip_weight() {
+        assert_eq!(spot_ip_weight("/api/v3/ticker/24hr", true), 1);
+        assert_eq!(spot_ip_weight("/api/v3/ticker/24hr", false), 40);
+        assert_eq!(spot_ip_weight("/api/v3/ticker/price", false), 2);
+        assert_eq!(spot_ip_weight("/api/v3/ticker/bookTicker", true), 1);
+        assert_eq!(spot_ip_weight("/api/v3/depth", false), 1);
+        assert_eq!(spot_request_weights("/api/v3/openOrders", false, true).len(), 2);
+    }
+
+    #[test]
+    fn test_futures_route() {
+        assert_eq!(futures_route("/api/v1/private/order/get/123"), "/api/v1/private/order/get");
+        assert_eq!(futures_route("/api/v1/private/position/open_positions"), "/api/v1/private/position/open_positions");
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::{Mexc, error::Result};
+use crate::market::{ExchangeInfo, SymbolInfo, SymbolRules};
+
+/// Exchange info indexed by symbol, base asset and quote asset.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolRegistry {
+    pub timestamp: u128,
+    symbols: HashMap<String, Arc<SymbolInfo>>,
+    by_base_asset: HashMap<String, Vec<Arc<SymbolInfo>>>,
+    by_quote_asset: HashMap<String, Vec<Arc<SymbolInfo>>>
+}
+
+impl From<ExchangeInfo> for SymbolRegistry {
+    fn from(info: ExchangeInfo) -> Self {
+        let mut registry = SymbolRegistry {
+            timestamp: info.timestamp,
+            ..Default::default()
+        };
+
+        for symbol in info.symbols {
+            let symbol = Arc::new(symbol);
+            registry.by_base_asset.entry(symbol.base_asset.clone()).or_default().push(Arc::clone(&symbol));
+            registry.by_quote_asset.entry(symbol.quote_asset.clone()).or_default().push(Arc::clone(&symbol));
+            registry.symbols.insert(symbol.symbol.clone(), symbol);
+        }
+        registry
+    }
+}
+
+impl SymbolRegistry {
+
+    pub async fn load(client: &Mexc) -> Result<Self> {
+        Ok(client.exchange_info().await?.into())
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<&Arc<SymbolInfo>> {
+        self.symbols.get(symbol)
+    }
+
+    pub fn rules(&self, symbol: &str) -> Option<SymbolRules> {
+        self.get(symbol).map(|info| info.rules())
+    }
+
+    pub fn by_base_asset(&self, base_asset: &str) -> &[Arc<SymbolInfo>] {
+        self.by_base_asset.get(base_asset).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn by_quote_asset(&self, quote_asset: &str) -> &[Arc<SymbolInfo>] {
+        self.by_quote_asset.get(quote_asset).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &Arc<SymbolInfo>> {
+        self.symbols.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
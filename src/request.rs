@@ -0,0 +1,193 @@
+use std::fmt::Display;
+use std::future::Future;
+use reqwest::{Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use crate::{Mexc, MexcError, retry, error::Result, orders::DEFAULT_RECV_WINDOW};
+use crate::rate_limit::spot_request_weights;
+use crate::utils::decode_response;
+
+/// Query parameters for a spot request, url encoded in insertion order.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Params {
+    pairs: Vec<(String, String)>,
+    recv_window: Option<u64>
+}
+
+impl Params {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(mut self, key: &str, value: impl Display) -> Self {
+        self.pairs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn add_opt(self, key: &str, value: Option<impl Display>) -> Self {
+        match value {
+            Some(value) => self.add(key, value),
+            None => self
+        }
+    }
+
+    /// Only used by signed requests, defaults to `DEFAULT_RECV_WINDOW`.
+    pub fn recv_window(mut self, recv_window: Option<u64>) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    /// Sorts by key, futures GET and DELETE requests are signed over the sorted query.
+    pub fn sorted(mut self) -> Self {
+        self.pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        self
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.pairs.iter().any(|(k, _)| k == key)
+    }
+
+    pub fn encode(&self) -> String {
+        url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(self.pairs.iter())
+            .finish()
+    }
+}
+
+/// Fetches `start..=end` in windows of at most `max_window` ms. A window that comes back with a full page
+/// of `limit` items is split in half and fetched again, so nothing is dropped whichever way the endpoint sorts.
+pub(crate) async fn collect_windows<T, F, Fut>(start: u128, end: u128, max_window: u128, limit: usize, mut fetch: F) -> Result<Vec<T>>
+where
+    F: FnMut(u128, u128) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+{
+    let mut windows = vec![];
+    let mut cursor = start;
+    while cursor <= end {
+        let window_end = end.min(cursor + max_window - 1);
+        windows.push((cursor, window_end));
+        cursor = window_end + 1;
+    }
+    windows.reverse();
+
+    let mut items = vec![];
+
+    while let Some((from, to)) = windows.pop() {
+        let page = fetch(from, to).await?;
+
+        if page.len() >= limit && to > from {
+            let mid = from + (to - from) / 2;
+            windows.push((mid + 1, to));
+            windows.push((from, mid));
+            continue;
+        }
+        items.extend(page);
+    }
+
+    Ok(items)
+}
+
+impl Mexc {
+
+    /// Shared request path, every spot request waits on the rate limiter and is retried per `retry_policy`.
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let request = request.build()?;
+        let signed = request.headers().contains_key("X-MEXC-APIKEY");
+        let has_symbol = request.url().query_pairs().any(|(key, _)| key == "symbol");
+        let weights = spot_request_weights(request.url().path(), has_symbol, signed);
+
+        retry::execute(&self.client, self.rate_limiter.as_ref(), &self.retry_policy, &weights, || {
+            let request = request.try_clone().ok_or_else(|| MexcError::InvalidRequest("request body can't be resent".to_string()));
+            Box::pin(std::future::ready(request))
+        }).await
+    }
+
+    /// Unsigned GET on a market data endpoint.
+    pub(crate) async fn public_request<T: DeserializeOwned>(&self, path: &str, params: Params) -> Result<T> {
+        let query = params.encode();
+
+        let url = if query.is_empty() {
+            format!("{}{path}", self.base_url)
+        } else {
+            format!("{}{path}?{query}", self.base_url)
+        };
+
+        let resp = self.send(self.client.get(url)).await?;
+        decode_response(resp).await
+    }
+
+    /// Appends recvWindow and timestamp, signs the encoded query and decodes the response into `T`.
+    /// The timestamp is taken after the rate limiter lets the request through.
+    pub(crate) async fn signed_request<T: DeserializeOwned>(&self, method: Method, path: &str, params: Params) -> Result<T> {
+        let api_key = self.api_key.as_ref().ok_or(MexcError::MissingCredentials("api key"))?;
+
+        let recv_window = params.recv_window.unwrap_or(DEFAULT_RECV_WINDOW);
+        let weights = spot_request_weights(path, params.contains("symbol"), true);
+
+        let resp = retry::execute(&self.client, self.rate_limiter.as_ref(), &self.retry_policy, &weights, || {
+            let method = method.clone();
+            let params = params.clone();
+
+            Box::pin(async move {
+                let timestamp = self.timestamp().await?;
+
+                let query = params
+                    .add("recvWindow", recv_window)
+                    .add("timestamp", timestamp)
+                    .encode();
+
+                let signed_query = self.sign_request(query)?;
+                let url = format!("{}{path}?{signed_query}", self.base_url);
+
+                let request = self.client
+                .request(method, url)
+                .header("X-MEXC-APIKEY", api_key)
+                .build()?;
+
+                Ok(request)
+            })
+        }).await?;
+
+        decode_response(resp).await.inspect_err(|err| {
+            if err.is_recv_window_error() {
+                self.time_sync.invalidate();
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode_params() {
+        let params = Params::new()
+            .add("symbol", "PLS USDT")
+            .add_opt("limit", Some(5))
+            .add_opt("startTime", None::<u64>)
+            .add("batchOrders", r#"[{"symbol":"PLSUSDT"}]"#);
+
+        assert_eq!(params.encode(), "symbol=PLS+USDT&limit=5&batchOrders=%5B%7B%22symbol%22%3A%22PLSUSDT%22%7D%5D");
+        assert_eq!(params.sorted().encode(), "batchOrders=%5B%7B%22symbol%22%3A%22PLSUSDT%22%7D%5D&limit=5&symbol=PLS+USDT");
+    }
+
+    #[tokio::test]
+    async fn test_collect_windows() {
+        let times: Vec<u128> = (0..100).collect();
+
+        let mut calls = 0;
+        let items = collect_windows(0, 99, 40, 10, |from, to| {
+            calls += 1;
+            // endpoint returns at most 10 items, latest first
+            let page: Vec<u128> = times.iter().rev().filter(|t| **t >= from && **t <= to).take(10).copied().collect();
+            async move { Ok(page) }
+        }).await.unwrap();
+
+        let mut sorted = items.clone();
+        sorted.sort();
+        assert_eq!(sorted, times);
+        assert!(calls > 3);
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+use futures::future::BoxFuture;
+use reqwest::{Client, Method, Request, Response, StatusCode};
+use reqwest::header::RETRY_AFTER;
+use crate::error::{MexcError, Result};
+use crate::rate_limit::{RateLimiter, RequestWeight};
+
+/// Exponential backoff for the shared request path. Only idempotent requests are retried,
+/// see `is_idempotent`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    /// Also caps the wait asked for by a `Retry-After` header
+    pub max_backoff: Duration,
+    pub multiplier: f64
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0
+        }
+    }
+}
+
+impl RetryPolicy {
+
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Backoff before retry number `attempt`, starting at 0.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        Duration::from_secs_f64(backoff).min(self.max_backoff)
+    }
+}
+
+/// GET, PUT and DELETE are safe to resend. A POST places an order or moves funds and is never resent here,
+/// `submit_order` looks a client id order up before resending it instead.
+pub fn is_idempotent(request: &Request) -> bool {
+    matches!(*request.method(), Method::GET | Method::PUT | Method::DELETE)
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// `Retry-After` in seconds, http dates are not used by MEXC. Capped at `max_backoff` by the caller.
+fn retry_after(resp: &Response) -> Option<Duration> {
+    resp.headers()
+        .get(RETRY_AFTER)?
+        .to_str().ok()?
+        .trim()
+        .parse::<u64>().ok()
+        .map(Duration::from_secs)
+}
+
+/// Sends the request produced by `build`, waiting on the rate limiter before every attempt and retrying
+/// transient failures of idempotent requests. `build` runs again for each attempt once the tokens are taken,
+/// so every send, retries included, is timestamped and signed inside its recvWindow.
+pub(crate) async fn execute<'a, F>(client: &Client, rate_limiter: &dyn RateLimiter, policy: &RetryPolicy, weights: &[RequestWeight], mut build: F) -> Result<Response>
+where
+    F: FnMut() -> BoxFuture<'a, Result<Request>>,
+{
+    let mut attempt = 0;
+
+    loop {
+        rate_limiter.acquire(weights).await;
+
+        let request = build().await?;
+        let retry = is_idempotent(&request) && attempt < policy.max_retries;
+
+        let wait = match client.execute(request).await {
+            Ok(resp) if retry && is_retryable_status(resp.status()) => {
+                retry_after(&resp).map_or_else(|| policy.backoff(attempt), |wait| wait.min(policy.max_backoff))
+            },
+            Ok(resp) => return Ok(resp),
+            Err(err) => {
+                let err = MexcError::from(err);
+                if !(retry && err.is_retryable()) {
+                    return Err(err);
+                }
+                policy.backoff(attempt)
+            }
+        };
+
+        tokio::time::sleep(wait).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_backoff_and_idempotency() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(800));
+        assert_eq!(policy.backoff(10), Duration::from_secs(5));
+
+        let client = Client::new();
+        let get = client.get("https://api.mexc.com/api/v3/depth?symbol=BTCUSDT").build().unwrap();
+        let post = client.post("https://api.mexc.com/api/v3/order?symbol=BTCUSDT").build().unwrap();
+        let delete = client.delete("https://api.mexc.com/api/v3/order?symbol=BTCUSDT&orderId=1").build().unwrap();
+
+        assert!(is_idempotent(&get));
+        assert!(is_idempotent(&delete));
+        assert!(!is_idempotent(&post));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{Mexc, error::Result, request::Params};
+use crate::account::AccountBalance;
+use crate::utils::{Number, format_number, parse_string_to_number};
+use crate::wallet::{AccountType, TransferId};
+use reqwest::Method;
+use serde::Deserialize;
+use std::fmt;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct VirtualSubAccount {
+    #[serde(rename = "subAccount")]
+    pub sub_account: String,
+    pub note: Option<String>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SubAccount {
+    #[serde(rename = "subAccount")]
+    pub sub_account: String,
+    #[serde(rename = "isFreeze")]
+    pub is_freeze: bool,
+    #[serde(rename = "createTime")]
+    pub create_time: u128,
+    pub uid: String
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SubAccountList {
+    #[serde(rename = "subAccounts")]
+    pub sub_accounts: Vec<SubAccount>
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiKeyPermission {
+    SPOT_ACCOUNT_READ,
+    SPOT_ACCOUNT_WRITE,
+    SPOT_DEAL_READ,
+    SPOT_DEAL_WRITE,
+    CONTRACT_ACCOUNT_READ,
+    CONTRACT_ACCOUNT_WRITE,
+    CONTRACT_DEAL_READ,
+    CONTRACT_DEAL_WRITE,
+    SPOT_TRANSFER_READ,
+    SPOT_TRANSFER_WRITE
+}
+
+impl fmt::Display for ApiKeyPermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let permission = match self {
+            ApiKeyPermission::SPOT_ACCOUNT_READ => "SPOT_ACCOUNT_READ",
+            ApiKeyPermission::SPOT_ACCOUNT_WRITE => "SPOT_ACCOUNT_WRITE",
+            ApiKeyPermission::SPOT_DEAL_READ => "SPOT_DEAL_READ",
+            ApiKeyPermission::SPOT_DEAL_WRITE => "SPOT_DEAL_WRITE",
+            ApiKeyPermission::CONTRACT_ACCOUNT_READ => "CONTRACT_ACCOUNT_READ",
+            ApiKeyPermission::CONTRACT_ACCOUNT_WRITE => "CONTRACT_ACCOUNT_WRITE",
+            ApiKeyPermission::CONTRACT_DEAL_READ => "CONTRACT_DEAL_READ",
+            ApiKeyPermission::CONTRACT_DEAL_WRITE => "CONTRACT_DEAL_WRITE",
+            ApiKeyPermission::SPOT_TRANSFER_READ => "SPOT_TRANSFER_READ",
+            ApiKeyPermission::SPOT_TRANSFER_WRITE => "SPOT_TRANSFER_WRITE",
+        };
+        f.write_str(permission)
+    }
+}
+
+/// Returned once on creation, the secret can't be queried again. `Debug` redacts the secret.
+#[derive(Deserialize, Clone)]
+pub struct SubAccountApiKeySecret {
+    #[serde(rename = "subAccount")]
+    pub sub_account: String,
+    pub note: Option<String>,
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    #[serde(rename = "secretKey")]
+    pub secret_key: String,
+    /// Comma separated `ApiKeyPermission`s
+    pub permissions: String,
+    pub ip: Option<String>,
+    #[serde(rename = "createTime")]
+    pub create_time: Option<u128>
+}
+
+impl fmt::Debug for SubAccountApiKeySecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubAccountApiKeySecret")
+            .field("sub_account", &self.sub_account)
+            .field("note", &self.note)
+            .field("api_key", &self.api_key)
+            .field("secret_key", &"<redacted>")
+            .field("permissions", &self.permissions)
+            .field("ip", &self.ip)
+            .field("create_time", &self.create_time)
+            .finish()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SubAccountApiKey {
+    pub note: Option<String>,
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    /// Comma separated `ApiKeyPermission`s
+    pub permissions: String,
+    pub ip: Option<String>,
+    // sic
+    #[serde(rename = "creatTime")]
+    pub create_time: Option<u128>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct SubAccountApiKeys {
+    #[serde(rename = "subAccount")]
+    api_keys: Vec<SubAccountApiKey>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SubAccountAssets {
+    pub balances: Vec<AccountBalance>
+}
+
+/// Source and destination of a sub-account universal transfer. An account left out is the master account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferRoute {
+    pub from_account: Option<String>,
+    pub to_account: Option<String>,
+    pub from_account_type: AccountType,
+    pub to_account_type: AccountType
+}
+
+impl TransferRoute {
+
+    pub fn new(from_account_type: AccountType, to_account_type: AccountType) -> Self {
+        TransferRoute {
+            from_account: None,
+            to_account: None,
+            from_account_type,
+            to_account_type
+        }
+    }
+
+    pub fn from_account(mut self, sub_account: impl Into<String>) -> Self {
+        self.from_account = Some(sub_account.into());
+        self
+    }
+
+    pub fn to_account(mut self, sub_account: impl Into<String>) -> Self {
+        self.to_account = Some(sub_account.into());
+        self
+    }
+
+    fn params(&self) -> Result<Params> {
+        Ok(Params::new()
+            .add_opt("fromAccount", self.from_account.as_deref())
+            .add_opt("toAccount", self.to_account.as_deref())
+            .add("fromAccountType", self.from_account_type.request_value()?)
+            .add("toAccountType", self.to_account_type.request_value()?))
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SubAccountTransfer {
+    #[serde(rename = "tranId")]
+    pub tran_id: String,
+    #[serde(rename = "fromAccount")]
+    pub from_account: Option<String>,
+    #[serde(rename = "toAccount")]
+    pub to_account: Option<String>,
+    #[serde(rename = "clientTranId")]
+    pub client_tran_id: Option<String>,
+    pub asset: String,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub amount: Number,
+    #[serde(rename = "fromAccountType")]
+    pub from_account_type: AccountType,
+    #[serde(rename = "toAccountType")]
+    pub to_account_type: AccountType,
+    /// SUCCESS, FAILED or WAIT
+    pub status: String,
+    pub timestamp: u128
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SubAccountTransferPage {
+    #[serde(rename = "totalCount")]
+    pub total_count: u64,
+    #[serde(rename = "result")]
+    pub transfers: Vec<SubAccountTransfer>
+}
+
+impl Mexc {
+
+    pub async fn create_sub_account(&self, sub_account: &str, note: &str) -> Result<VirtualSubAccount> {
+        let params = Params::new()
+            .add("subAccount", sub_account)
+            .add("note", note);
+
+        self.signed_request(Method::POST, "/api/v3/sub-account/virtualSubAccount", params).await
+    }
+
+    /// page starts at 1, limit: default 10; max 200
+    pub async fn get_sub_accounts(&self, sub_account: Option<&str>, is_freeze: Option<bool>, page: Option<u32>, limit: Option<u32>) -> Result<Vec<SubAccount>> {
+        let params = Params::new()
+            .add_opt("subAccount", sub_account)
+            .add_opt("isFreeze", is_freeze)
+            .add_opt("page", page)
+            .add_opt("limit", limit);
+
+        let list: SubAccountList = self.signed_request(Method::GET, "/api/v3/sub-account/list", params).await?;
+        Ok(list.sub_accounts)
+    }
+
+    /// `ips` restricts the key to those addresses, MEXC allows up to 20.
+    pub async fn create_sub_account_api_key(&self, sub_account: &str, note: &str, permissions: &[ApiKeyPermission], ips: &[&str]) -> Result<SubAccountApiKeySecret> {
+        let permissions = permissions.iter().map(ToString::to_string).collect::<Vec<_>>().join(",");
+        let ips = (!ips.is_empty()).then(|| ips.join(","));
+
+        let params = Params::new()
+            .add("subAccount", sub_account)
+            .add("note", note)
+            .add("permissions", permissions)
+            .add_opt("ip", ips);
+
+        self.signed_request(Method::POST, "/api/v3/sub-account/apiKey", params).await
+    }
+
+    pub async fn get_sub_account_api_keys(&self, sub_account: &str) -> Result<Vec<SubAccountApiKey>> {
+        let params = Params::new().add("subAccount", sub_account);
+
+        let keys: SubAccountApiKeys = self.signed_request(Method::GET, "/api/v3/sub-account/apiKey", params).await?;
+        Ok(keys.api_keys)
+    }
+
+    pub async fn delete_sub_account_api_key(&self, sub_account: &str, api_key: &str) -> Result<()> {
+        let params = Params::new()
+            .add("subAccount", sub_account)
+            .add("apiKey", api_key);
+
+        let _: VirtualSubAccount = self.signed_request(Method::DELETE, "/api/v3/sub-account/apiKey", params).await?;
+        Ok(())
+    }
+
+    /// Moves `amount` of `asset` along `route`, returns the transfer id.
+    pub async fn sub_account_transfer(&self, route: &TransferRoute, asset: &str, amount: Number) -> Result<String> {
+        let params = route.params()?
+            .add("asset", asset)
+            .add("amount", format_number(amount));
+
+        let resp: TransferId = self.signed_request(Method::POST, "/api/v3/capital/sub-account/universalTransfer", params).await?;
+        Ok(resp.tran_id)
+    }
+
+    /// One page of transfers along `route`, MEXC defaults to the last 7 days. page starts at 1, limit: default 500; max 500
+    pub async fn get_sub_account_transfer_history(&self, route: &TransferRoute, start: Option<u128>, end: Option<u128>, page: Option<u32>, limit: Option<u32>) -> Result<SubAccountTransferPage> {
+        let params = route.params()?
+            .add_opt("startTime", start)
+            .add_opt("endTime", end)
+            .add_opt("page", page)
+            .add_opt("limit", limit);
+
+        self.signed_request(Method::GET, "/api/v3/capital/sub-account/universalTransfer", params).await
+    }
+
+    /// Balances of `sub_account` in its SPOT or FUTURES account.
+    pub async fn get_sub_account_assets(&self, sub_account: &str, account_type: AccountType) -> Result<Vec<AccountBalance>> {
+        let params = Params::new()
+            .add("subAccount", sub_account)
+            .add("accountType", account_type.request_value()?);
+
+        let assets: SubAccountAssets = self.signed_request(Method::GET, "/api/v3/sub-account/asset", params).await?;
+        Ok(assets.balances)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::utils::{number, unlock_keys};
+
+    #[test]
+    fn test_decode_sub_accounts() {
+        let json = r#"{"subAccounts":[{"subAccount":"mexc1","isFreeze":false,"createTime":1544433328000,"uid":"49910594"}]}"#;
+        let list: SubAccountList = serde_json::from_str(json).unwrap();
+        assert_eq!(list.sub_accounts[0].uid, "49910594");
+
+        let json = r#"{"subAccount":[{"note":"v5","apiKey":"arg13sdfgs","permissions":"SPOT_ACCOUNT_READ,SPOT_DEAL_WRITE","ip":"17.1.1.1,2.2.2.2","creatTime":1665374443000}]}"#;
+        let keys: SubAccountApiKeys = serde_json::from_str(json).unwrap();
+        assert_eq!(keys.api_keys[0].create_time, Some(1665374443000));
+
+        let json = r#"{"totalCount":1,"result":[{"tranId":"11945860693","fromAccount":"master@test.com","toAccount":"subaccount1@test.com","clientTranId":"test","asset":"BTC","amount":"0.1","fromAccountType":"SPOT","toAccountType":"FUTURES","fromSymbol":"SPOT","toSymbol":"FUTURES","status":"SUCCESS","timestamp":1544433325000}]}"#;
+        let page: SubAccountTransferPage = serde_json::from_str(json).unwrap();
+        assert_eq!(page.transfers[0].amount, number(0.1));
+
+        let route = TransferRoute::new(AccountType::SPOT, AccountType::FUTURES).to_account("mexc1");
+        assert_eq!(route.params().unwrap().encode(), "toAccount=mexc1&fromAccountType=SPOT&toAccountType=FUTURES");
+        assert!(TransferRoute::new(AccountType::OTHER, AccountType::SPOT).params().is_err());
+
+        let json = r#"{"subAccount":"mexc1","note":"v5","apiKey":"arg13sdfgs","secretKey":"s3cr3t","permissions":"SPOT_ACCOUNT_READ","ip":null,"createTime":1665374443000}"#;
+        let created: SubAccountApiKeySecret = serde_json::from_str(json).unwrap();
+        assert_eq!(created.secret_key, "s3cr3t");
+        assert!(!format!("{created:?}").contains("s3cr3t"));
+    }
+
+    #[tokio::test]
+    async fn test_sub_accounts() {
+        let (key, secret) = unlock_keys().unwrap();
+        let client = Mexc::new(Some(key),Some(secret),None).unwrap();
+
+        let sub_accounts = client.get_sub_accounts(None, None, None, None).await.unwrap();
+        dbg!(&sub_accounts);
+
+        if let Some(sub_account) = sub_accounts.first() {
+            let assets = client.get_sub_account_assets(&sub_account.sub_account, AccountType::SPOT).await.unwrap();
+            dbg!(assets);
+
+            let keys = client.get_sub_account_api_keys(&sub_account.sub_account).await.unwrap();
+            dbg!(keys);
+        }
+    }
+}
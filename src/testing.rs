@@ -3,12 +3,14 @@
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
+    use crate::market::Interval;
     use crate::orders::CancelledOrder;
     use crate::orders::Order;
     use crate::orders::OrderSide;
     use crate::orders::OrderType;
     use crate::orders::OrderReceipt;
-    use crate::utils::round;
+    use crate::orders::{OrderQuery, OrderRef, OrderRequest, OrderStatus};
+    use crate::utils::{number, round};
     use crate::{utils::unlock_keys, Mexc};
 
     async fn sleep(secs: f64) {
@@ -25,6 +27,24 @@ mod tests {
         dbg!(time);
     }
 
+    #[tokio::test]
+    pub async fn test_sync_time() {
+
+        let client = Mexc::new(None,None,None).unwrap();
+
+        let offset = client.sync_time().await.unwrap();
+        dbg!(offset);
+    }
+
+    #[test]
+    pub fn test_builder_base_url() {
+        let client = Mexc::builder().base_url("http://127.0.0.1:8080/").build().unwrap();
+        assert_eq!(client.base_url, "http://127.0.0.1:8080");
+
+        let client = Mexc::new(None,None,None).unwrap();
+        assert_eq!(client.base_url, crate::PROD_API_URL);
+    }
+
     #[tokio::test]
     pub async fn test_ping() {
 
@@ -41,6 +61,46 @@ mod tests {
         dbg!(info);
     }
 
+    #[tokio::test]
+    pub async fn test_get_klines_range() {
+        let client = Mexc::new(None,None,None).unwrap();
+
+        let end = crate::utils::get_timestamp();
+        let start = end - 2 * 24 * 3600 * 1000;
+
+        let klines = client.get_klines_range("BTCUSDT", Interval::OneMinute, start, end).await.unwrap();
+        dbg!(klines.len(), klines.first(), klines.last());
+    }
+
+    #[tokio::test]
+    pub async fn test_get_trades() {
+        let client = Mexc::new(None,None,None).unwrap();
+
+        let trades = client.get_recent_trades("BTCUSDT", Some(10)).await.unwrap();
+        dbg!(trades);
+
+        let end = crate::utils::get_timestamp();
+        let agg_trades = client.get_aggregate_trades_range("BTCUSDT", end - 2 * 3600 * 1000, end).await.unwrap();
+        dbg!(agg_trades.len(), agg_trades.last());
+    }
+
+    #[tokio::test]
+    pub async fn test_get_tickers() {
+        let client = Mexc::new(None,None,None).unwrap();
+
+        let book = client.get_book_ticker(Some("PLSUSDT")).await.unwrap();
+        dbg!(book);
+
+        let prices = client.get_price_ticker(None).await.unwrap();
+        dbg!(prices.len());
+
+        let stats = client.get_ticker_24hr(Some("PLSUSDT")).await.unwrap();
+        dbg!(stats);
+
+        let avg = client.get_avg_price("PLSUSDT").await.unwrap();
+        dbg!(avg);
+    }
+
     #[tokio::test]
     pub async fn test_exchange_info() {
         let client = Mexc::new(None,None,None).unwrap();
@@ -61,7 +121,7 @@ mod tests {
         let (key, secret) = unlock_keys().unwrap();
         let client = Mexc::new(Some(key),Some(secret),None).unwrap();
 
-        let receipe = client.submit_order("PLSUSDT", OrderSide::SELL, OrderType::LIMIT, 0.00009512, 599971.13, None).await.unwrap();
+        let receipe = client.submit_order(&OrderRequest::limit("PLSUSDT", OrderSide::SELL, number(0.00009512), number(599971.13)), None).await.unwrap();
         dbg!(receipe);
     }
 
@@ -73,6 +133,48 @@ mod tests {
         dbg!(receipe);
     }
 
+    #[test]
+    pub fn test_order_request() {
+        let order = OrderRequest::market_quote("PLSUSDT", OrderSide::BUY, number(25.5)).with_client_order_id("oms1");
+        assert_eq!(order.params().encode(), "symbol=PLSUSDT&side=BUY&type=MARKET&quoteOrderQty=25.5&newClientOrderId=oms1");
+
+        let order = OrderRequest::market("PLSUSDT", OrderSide::SELL, number(1000.0));
+        assert_eq!(order.params().encode(), "symbol=PLSUSDT&side=SELL&type=MARKET&quantity=1000");
+
+        let order = OrderRequest::limit_maker("PLSUSDT", OrderSide::SELL, number(0.00009512), number(599971.13));
+        assert_eq!(serde_json::to_string(&order).unwrap(), r#"{"symbol":"PLSUSDT","side":"SELL","type":"LIMIT_MAKER","price":"0.00009512","quantity":"599971.13"}"#);
+    }
+
+    #[test]
+    pub fn test_decode_order_query() {
+        let oq = r#"[{"symbol":"PLSUSDT","orderId":"C02__426199983784497153065","orderListId":-1,"clientOrderId":null,"price":"0.00009712","origQty":"299985.56","executedQty":"100000","cummulativeQuoteQty":"9.712","status":"PARTIALLY_FILLED","timeInForce":null,"type":"LIMIT","side":"SELL","stopPrice":null,"icebergQty":null,"time":1717363075282,"updateTime":1717363076282,"isWorking":true,"origQuoteOrderQty":"29.13"}]"#;
+
+        let orders: Vec<OrderQuery> = serde_json::from_str(oq).unwrap();
+        assert_eq!(orders[0].status, OrderStatus::PARTIALLY_FILLED);
+        assert!(!orders[0].status.is_final());
+        dbg!(orders);
+    }
+
+    #[tokio::test]
+    pub async fn test_order_history() {
+        let (key, secret) = unlock_keys().unwrap();
+        let client = Mexc::new(Some(key),Some(secret),None).unwrap();
+
+        let end = crate::utils::get_timestamp();
+        let start = end - 30 * 24 * 3600 * 1000;
+
+        let orders = client.get_all_orders_range("PLSUSDT", start, end, None).await.unwrap();
+        dbg!(orders.len());
+
+        if let Some(order) = orders.last() {
+            let order = client.query_order("PLSUSDT", order.order_id.as_str(), None).await.unwrap();
+            dbg!(order);
+        }
+
+        let trades = client.get_my_trades_range("PLSUSDT", start, end, None).await.unwrap();
+        dbg!(trades);
+    }
+
     #[test]
     pub fn test_decode_cancelled_orders() {
         let co = r#"[{"symbol":"PLSUSDT","orderId":"C02__426199983784497153065","price":"0.00009712","origQty":"299985.56","type":"LIMIT","side":"SELL","executedQty":"0","cummulativeQuoteQty":"0","status":"NEW"},{"symbol":"PLSUSDT","orderId":"C02__426199982572318720065","price":"0.00009512","origQty":"299985.56","type":"LIMIT","side":"SELL","executedQty":"0","cummulativeQuoteQty":"0","status":"NEW"}]"#;
@@ -87,10 +189,10 @@ mod tests {
 
         let q = 599971.13;
 
-        let order_1 = client.submit_order("PLSUSDT", OrderSide::SELL, OrderType::LIMIT, 0.00009512, round(q/2.0, 2), None).await.unwrap();
+        let order_1 = client.submit_order(&OrderRequest::limit("PLSUSDT", OrderSide::SELL, number(0.00009512), number(round(q/2.0, 2))), None).await.unwrap();
         dbg!(order_1);
 
-        let order_2 = client.submit_order("PLSUSDT", OrderSide::SELL, OrderType::LIMIT, 0.00009712, round(q/2.0, 2), None).await.unwrap();
+        let order_2 = client.submit_order(&OrderRequest::limit("PLSUSDT", OrderSide::SELL, number(0.00009712), number(round(q/2.0, 2))), None).await.unwrap();
         dbg!(order_2);
 
         sleep(0.4).await;
@@ -106,7 +208,7 @@ mod tests {
 
         let q = 599971.13;
 
-        let order_1 = client.submit_order("PLSUSDT", OrderSide::SELL, OrderType::LIMIT, 0.00009512, round(q/2.0, 2), None).await.unwrap();
+        let order_1 = client.submit_order(&OrderRequest::limit("PLSUSDT", OrderSide::SELL, number(0.00009512), number(round(q/2.0, 2))), None).await.unwrap();
         dbg!(&order_1);
 
         sleep(0.4).await;
@@ -115,6 +217,36 @@ mod tests {
         dbg!(co);
     }
 
+    #[tokio::test]
+    pub async fn test_test_order() {
+        let (key, secret) = unlock_keys().unwrap();
+        let client = Mexc::new(Some(key),Some(secret),None).unwrap();
+
+        client.test_order(&OrderRequest::limit("PLSUSDT", OrderSide::SELL, number(0.00009512), number(599971.13)), None).await.unwrap();
+
+        let err = client.test_order(&OrderRequest::limit("PLSUSDT", OrderSide::SELL, number(0.00009512), number(0.001)), None).await.unwrap_err();
+        dbg!(err);
+    }
+
+    #[tokio::test]
+    pub async fn test_client_order_id() {
+        let (key, secret) = unlock_keys().unwrap();
+        let client = Mexc::new(Some(key),Some(secret),None).unwrap();
+
+        let client_id = format!("oms{}", crate::utils::get_timestamp());
+
+        let order_1 = client.submit_order(&OrderRequest::limit("PLSUSDT", OrderSide::SELL, number(0.00009512), number(300000.0)).with_client_order_id(&client_id), None).await.unwrap();
+        assert_eq!(order_1.client_order_id.as_deref(), Some(client_id.as_str()));
+
+        sleep(0.4).await;
+
+        let query = client.query_order("PLSUSDT", OrderRef::ClientOrderId(client_id.clone()), None).await.unwrap();
+        dbg!(query);
+
+        let co = client.cancel_order("PLSUSDT", OrderRef::ClientOrderId(client_id), None).await.unwrap();
+        dbg!(co);
+    }
+
     #[tokio::test]
     pub async fn test_batch_order() {
 
@@ -124,22 +256,24 @@ mod tests {
         let mut orders = vec![];
 
         let q = 599971.13;
-        let q = round(q/2.0, 2);
+        let q = number(round(q/2.0, 2));
 
         orders.push(Order {
             symbol: "PLSUSDT".into(),
-            price: 0.00009512,
+            price: number(0.00009512),
             quantity: q,
             side: OrderSide::SELL,
-            order_type: OrderType::LIMIT
+            order_type: OrderType::LIMIT,
+            client_order_id: None
         });
         
         orders.push(Order {
             symbol: "PLSUSDT".into(),
-            price: 0.00009712,
+            price: number(0.00009712),
             quantity: q,
             side: OrderSide::SELL,
-            order_type: OrderType::LIMIT
+            order_type: OrderType::LIMIT,
+            client_order_id: None
         });
 
         let res = client.batch_orders(orders, None).await.unwrap();
@@ -153,7 +287,7 @@ mod tests {
 
         let q = 599971.13;
 
-        let order_1 = client.submit_order("PLSUSDT", OrderSide::SELL, OrderType::LIMIT, 0.00009512, round(q/2.0, 2), None).await.unwrap();
+        let order_1 = client.submit_order(&OrderRequest::limit("PLSUSDT", OrderSide::SELL, number(0.00009512), number(round(q/2.0, 2))), None).await.unwrap();
         dbg!(&order_1);
 
         sleep(0.4).await;
@@ -0,0 +1,171 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use crate::utils::get_timestamp;
+
+/// Used by the client builders unless `time_sync_interval` is set.
+pub const DEFAULT_TIME_SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Snapshot of the estimated difference between the exchange clock and the local clock.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockOffset {
+    /// Server time minus local time in milliseconds
+    pub offset_ms: i64,
+    pub round_trip: Duration,
+    pub synced_at: Option<Instant>
+}
+
+/// Tracks the server clock offset applied to `timestamp` / `Request-Time` on signed requests.
+/// With an interval set the clients resync lazily before a signed request once the last sync is older than it,
+/// the first signed request always syncs.
+#[derive(Debug, Default)]
+pub struct TimeSync {
+    offset_ms: AtomicI64,
+    round_trip_us: AtomicU64,
+    synced_at: Mutex<Option<Instant>>,
+    invalidated: AtomicBool,
+    sync_lock: tokio::sync::Mutex<()>,
+    interval: Option<Duration>
+}
+
+impl TimeSync {
+
+    pub fn new(interval: Option<Duration>) -> Self {
+        Self {
+            interval,
+            ..Default::default()
+        }
+    }
+
+    /// Local wall clock corrected by the current offset, in milliseconds.
+    pub fn now(&self) -> u128 {
+        let local = get_timestamp() as i128;
+        (local + self.offset_ms.load(Ordering::Relaxed) as i128).max(0) as u128
+    }
+
+    pub fn needs_sync(&self) -> bool {
+        if self.invalidated.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        let Some(interval) = self.interval else {
+            return false;
+        };
+
+        match *self.synced_at.lock().unwrap() {
+            Some(synced_at) => synced_at.elapsed() >= interval,
+            None => true
+        }
+    }
+
+    /// Forces a resync before the next signed request, e.g. after the exchange rejected a timestamp.
+    /// The current offset stays in use until the resync succeeds.
+    pub fn invalidate(&self) {
+        self.invalidated.store(true, Ordering::Relaxed);
+    }
+
+    /// Runs `sync` when the offset is stale. Concurrent callers queue behind the one syncing
+    /// and reuse its result instead of each hitting the server. A failed resync keeps the last offset,
+    /// the error is only returned when no sync has succeeded yet.
+    pub async fn sync_if_needed<F, Fut, E>(&self, sync: F) -> Result<(), E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<ClockOffset, E>>,
+    {
+        if !self.needs_sync() {
+            return Ok(());
+        }
+
+        let _guard = self.sync_lock.lock().await;
+
+        // The caller holding the lock before us may already have synced
+        if self.needs_sync() {
+            if let Err(err) = sync().await {
+                if self.synced_at.lock().unwrap().is_none() {
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a server time sample. `sent_at` is the local time in ms when the request left,
+    /// the server is assumed to have stamped the response half way through the round trip.
+    pub fn record(&self, sent_at: u128, round_trip: Duration, server_time: u128) -> ClockOffset {
+        let midpoint = sent_at as i128 + (round_trip.as_millis() / 2) as i128;
+        let offset_ms = (server_time as i128 - midpoint) as i64;
+        let synced_at = Instant::now();
+
+        self.offset_ms.store(offset_ms, Ordering::Relaxed);
+        self.round_trip_us.store(round_trip.as_micros() as u64, Ordering::Relaxed);
+        *self.synced_at.lock().unwrap() = Some(synced_at);
+        self.invalidated.store(false, Ordering::Relaxed);
+
+        ClockOffset {
+            offset_ms,
+            round_trip,
+            synced_at: Some(synced_at)
+        }
+    }
+
+    pub fn offset(&self) -> ClockOffset {
+        ClockOffset {
+            offset_ms: self.offset_ms.load(Ordering::Relaxed),
+            round_trip: Duration::from_micros(self.round_trip_us.load(Ordering::Relaxed)),
+            synced_at: *self.synced_at.lock().unwrap()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_record_offset() {
+        let sync = TimeSync::new(Some(Duration::from_secs(60)));
+        assert!(sync.needs_sync());
+
+        let offset = sync.record(1_000_000, Duration::from_millis(100), 1_000_550);
+        assert_eq!(offset.offset_ms, 500);
+        assert_eq!(sync.offset().round_trip, Duration::from_millis(100));
+        assert!(!sync.needs_sync());
+
+        sync.invalidate();
+        assert!(sync.needs_sync());
+        sync.record(1_000_000, Duration::from_millis(100), 1_000_550);
+        assert!(!sync.needs_sync());
+
+        let disabled = TimeSync::new(None);
+        assert!(!disabled.needs_sync());
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_sync() {
+        let sync = TimeSync::new(Some(DEFAULT_TIME_SYNC_INTERVAL));
+        let calls = AtomicU64::new(0);
+
+        let syncs = (0..5).map(|_| sync.sync_if_needed(|| async {
+            calls.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok::<_, ()>(sync.record(get_timestamp(), Duration::from_millis(10), get_timestamp()))
+        }));
+        futures::future::join_all(syncs).await;
+
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        assert!(!sync.needs_sync());
+    }
+
+    #[tokio::test]
+    async fn test_failed_resync_keeps_offset() {
+        let sync = TimeSync::new(Some(Duration::ZERO));
+        assert_eq!(sync.sync_if_needed(|| async { Err::<ClockOffset, _>("down") }).await, Err("down"));
+
+        sync.record(1_000_000, Duration::from_millis(100), 1_000_550);
+        assert!(sync.needs_sync());
+        assert_eq!(sync.sync_if_needed(|| async { Err::<ClockOffset, _>("down") }).await, Ok(()));
+        assert_eq!(sync.offset().offset_ms, 500);
+    }
+}
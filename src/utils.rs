@@ -4,12 +4,28 @@ use std::env;
 use std::time::{SystemTime, UNIX_EPOCH};
 use serde_json::Value;
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use reqwest::Response;
+use crate::error::{MexcError, Result};
+
+/// Type of prices, quantities and balances: `f64` by default, `rust_decimal::Decimal` with the `decimal` feature.
+#[cfg(not(feature = "decimal"))]
+pub type Number = f64;
+
+#[cfg(feature = "decimal")]
+pub type Number = rust_decimal::Decimal;
+
+#[cfg(not(feature = "decimal"))]
+pub const ZERO: Number = 0.0;
+
+#[cfg(feature = "decimal")]
+pub const ZERO: Number = rust_decimal::Decimal::ZERO;
 
 pub fn get_timestamp() -> u128 {
     SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis()
 }
 
-pub fn parse_string_to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+pub fn parse_string_to_f64<'de, D>(deserializer: D) -> std::result::Result<f64, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -22,21 +38,257 @@ where
     }
 }
 
-pub fn serialize_f64_as_string<S>(x: &f64, serializer: S) -> Result<S::Ok, S::Error>
+/// Like `parse_string_to_f64` but rejects null, zero and negative values, for fields that are divided by.
+pub fn parse_positive_f64<'de, D>(deserializer: D) -> std::result::Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let x = parse_string_to_f64(deserializer)?;
+    if x > 0.0 {
+        Ok(x)
+    } else {
+        Err(serde::de::Error::custom(format!("expected a positive number, got {x}")))
+    }
+}
+
+#[cfg(not(feature = "decimal"))]
+pub fn parse_number(s: &str) -> std::result::Result<Number, String> {
+    s.parse::<f64>().map_err(|err| err.to_string())
+}
+
+/// Parses a decimal string, falling back to scientific notation. Trailing zeros are dropped.
+#[cfg(feature = "decimal")]
+pub fn parse_number(s: &str) -> std::result::Result<Number, String> {
+    use std::str::FromStr;
+    rust_decimal::Decimal::from_str(s)
+        .or_else(|_| rust_decimal::Decimal::from_scientific(s))
+        .map(|d| d.normalize())
+        .map_err(|err| err.to_string())
+}
+
+/// Converts an `f64` literal, e.g. `number(0.00009512)`, into a `Number`.
+#[cfg(not(feature = "decimal"))]
+pub fn number(x: f64) -> Number {
+    x
+}
+
+#[cfg(feature = "decimal")]
+pub fn number(x: f64) -> Number {
+    rust_decimal::Decimal::try_from(x).map(|d| d.normalize()).unwrap_or_default()
+}
+
+/// Plain decimal string as MEXC expects in requests, never in scientific notation.
+#[cfg(not(feature = "decimal"))]
+pub fn format_number(x: Number) -> String {
+    x.to_string()
+}
+
+#[cfg(feature = "decimal")]
+pub fn format_number(x: Number) -> String {
+    x.normalize().to_string()
+}
+
+pub fn parse_string_to_number<'de, D>(deserializer: D) -> std::result::Result<Number, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Value = Deserialize::deserialize(deserializer)?;
+    match value {
+        Value::String(s) if s.is_empty() => Ok(ZERO),
+        Value::String(s) => parse_number(&s).map_err(serde::de::Error::custom),
+        Value::Number(n) => parse_number(&n.to_string()).map_err(serde::de::Error::custom),
+        Value::Null => Ok(ZERO),
+        _ => Err(serde::de::Error::custom("Invalid type")),
+    }
+}
+
+pub fn parse_optional_number<'de, D>(deserializer: D) -> std::result::Result<Option<Number>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Value = Deserialize::deserialize(deserializer)?;
+    match value {
+        Value::Null => Ok(None),
+        Value::String(s) if s.is_empty() => Ok(None),
+        Value::String(s) => parse_number(&s).map(Some).map_err(serde::de::Error::custom),
+        Value::Number(n) => parse_number(&n.to_string()).map(Some).map_err(serde::de::Error::custom),
+        _ => Err(serde::de::Error::custom("Invalid type")),
+    }
+}
+
+/// Deserializes null as the type's default.
+pub fn null_as_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Deserializes a list, dropping entries that don't decode, e.g. order types or filters added by the exchange later.
+pub fn skip_unknown<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let values: Vec<Value> = Deserialize::deserialize(deserializer)?;
+    Ok(values.into_iter().filter_map(|v| serde_json::from_value(v).ok()).collect())
+}
+
+pub fn serialize_f64_as_string<S>(x: &f64, serializer: S) -> std::result::Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
     serializer.serialize_str(&x.to_string())
 }
 
+pub fn serialize_number_as_string<S>(x: &Number, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&format_number(*x))
+}
+
+pub fn serialize_optional_number<S>(x: &Option<Number>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match x {
+        Some(x) => serializer.serialize_str(&format_number(*x)),
+        None => serializer.serialize_none()
+    }
+}
 
-pub fn unlock_keys() -> anyhow::Result<(String, String)>{
+pub fn unlock_keys() -> std::result::Result<(String, String), env::VarError>{
     let key: String = env::var("mexcn_accesskey")?;
     let secret: String = env::var("mexn_secretkey")?;
     Ok((key, secret))
 }
 
+/// Checks the status of a spot response and decodes the body, keeping the raw text on failure.
+pub async fn decode_response<T: DeserializeOwned>(resp: Response) -> Result<T> {
+    let status = resp.status();
+    let body = resp.text().await?;
+
+    if !status.is_success() {
+        return Err(MexcError::from_spot_response(status, body));
+    }
+
+    serde_json::from_str(&body).map_err(|source| MexcError::Decode { source, body })
+}
+
+pub fn trim_base_url(url: &str) -> String {
+    url.trim_end_matches('/').to_string()
+}
+
+/// Rounds to `decimals` places. Goes through the decimal string so that e.g. 0.29 stays 0.29.
+#[cfg(not(feature = "decimal"))]
+pub fn round_to(x: Number, decimals: u32) -> Number {
+    format!("{:.*}", decimals as usize, x).parse().unwrap_or(x)
+}
+
+/// Truncates to `decimals` places, tolerating binary representation error just below a step.
+#[cfg(not(feature = "decimal"))]
+pub fn floor_to(x: Number, decimals: u32) -> Number {
+    let y = 10f64.powi(decimals as i32);
+    let scaled = x * y;
+    let floored = (scaled + scaled.abs() * f64::EPSILON * 4.0).floor();
+    round_to(floored / y, decimals)
+}
+
+#[cfg(feature = "decimal")]
+pub fn round_to(x: Number, decimals: u32) -> Number {
+    x.round_dp_with_strategy(decimals, rust_decimal::RoundingStrategy::MidpointAwayFromZero).normalize()
+}
+
+#[cfg(feature = "decimal")]
+pub fn floor_to(x: Number, decimals: u32) -> Number {
+    x.round_dp_with_strategy(decimals, rust_decimal::RoundingStrategy::ToNegativeInfinity).normalize()
+}
+
+/// `10^-decimals`, the step size for a precision given in decimal places.
+#[cfg(not(feature = "decimal"))]
+pub fn step_size(decimals: u32) -> Number {
+    10f64.powi(-(decimals as i32))
+}
+
+#[cfg(feature = "decimal")]
+pub fn step_size(decimals: u32) -> Number {
+    rust_decimal::Decimal::new(1, decimals)
+}
+
+/// Decimal places of a step such as `0.001`.
+#[cfg(not(feature = "decimal"))]
+pub fn step_decimals(step: Number) -> u32 {
+    step.to_string().split_once('.').map_or(0, |(_, decimals)| decimals.len() as u32)
+}
+
+#[cfg(feature = "decimal")]
+pub fn step_decimals(step: Number) -> u32 {
+    step.normalize().scale()
+}
+
+/// Rounds to the nearest multiple of `step`, e.g. a price tick. A step of 0 leaves `x` as is.
+#[cfg(not(feature = "decimal"))]
+pub fn round_to_step(x: Number, step: Number) -> Number {
+    if step <= ZERO {
+        return x;
+    }
+    round_to((x / step).round() * step, step_decimals(step))
+}
+
+#[cfg(feature = "decimal")]
+pub fn round_to_step(x: Number, step: Number) -> Number {
+    if step <= ZERO {
+        return x;
+    }
+    ((x / step).round_dp_with_strategy(0, rust_decimal::RoundingStrategy::MidpointAwayFromZero) * step).normalize()
+}
+
+/// Floors to a multiple of `step`, e.g. a quantity step, with the same tolerance as `floor_to`.
+#[cfg(not(feature = "decimal"))]
+pub fn floor_to_step(x: Number, step: Number) -> Number {
+    if step <= ZERO {
+        return x;
+    }
+    let steps = x / step;
+    let floored = (steps + steps.abs() * f64::EPSILON * 4.0).floor();
+    round_to(floored * step, step_decimals(step))
+}
+
+#[cfg(feature = "decimal")]
+pub fn floor_to_step(x: Number, step: Number) -> Number {
+    if step <= ZERO {
+        return x;
+    }
+    ((x / step).floor() * step).normalize()
+}
+
 pub fn round(x: f64, decimals: u32) -> f64 {
     let y = 10i64.pow(decimals) as f64;
     (x * y).floor() / y
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_number_round_trip() {
+        for s in ["0.00009512", "599971.13", "3650.1", "0.3"] {
+            assert_eq!(format_number(parse_number(s).unwrap()), s);
+        }
+
+        assert_eq!(format_number(floor_to(number(599971.139), 2)), "599971.13");
+        assert_eq!(format_number(round_to(number(0.0000951256), 8)), "0.00009513");
+        assert_eq!(step_size(2), number(0.01));
+
+        assert_eq!(step_decimals(number(0.0001)), 4);
+        assert_eq!(step_decimals(number(10.0)), 0);
+        assert_eq!(format_number(round_to_step(number(0.0000951256), step_size(8))), "0.00009513");
+        assert_eq!(format_number(floor_to_step(number(599971.139), number(0.01))), "599971.13");
+        assert_eq!(format_number(floor_to_step(number(1.3), number(0.1))), "1.3");
+        assert_eq!(format_number(floor_to_step(number(599975.0), number(10.0))), "599970");
+        assert_eq!(format_number(round_to_step(number(12.26), number(0.05))), "12.25");
+    }
+}
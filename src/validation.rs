@@ -0,0 +1,237 @@
+use thiserror::Error;
+use crate::{Mexc, error::Result};
+use crate::market::{SymbolInfo, SymbolRules};
+use crate::orders::{Order, OrderReceipt, OrderRequest, OrderType};
+use crate::utils::{Number, ZERO, floor_to, floor_to_step, round_to_step, step_size};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum OrderValidationError {
+    #[error("{symbol} is not open for spot trading")]
+    SymbolNotTrading { symbol: String },
+
+    #[error("{symbol} does not support {order_type} orders")]
+    UnsupportedOrderType { symbol: String, order_type: OrderType },
+
+    #[error("order is for {order_symbol} but symbol info is for {symbol}")]
+    SymbolMismatch { symbol: String, order_symbol: String },
+
+    #[error("price {price} is not positive after rounding to a tick of {tick}")]
+    InvalidPrice { price: Number, tick: Number },
+
+    #[error("quantity {quantity} is not positive after rounding to a step of {step}")]
+    InvalidQuantity { quantity: Number, step: Number },
+
+    #[error("order value {notional} is below the minimum of {min}")]
+    BelowMinNotional { notional: Number, min: Number },
+
+    #[error("order value {notional} is above the maximum of {max}")]
+    AboveMaxNotional { notional: Number, max: Number }
+}
+
+/// Price and quantity after rounding to the symbol rules.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalizedOrder {
+    pub price: Number,
+    pub quantity: Number
+}
+
+impl SymbolInfo {
+
+    /// Rounds price to `price_tick` and floors quantity to `quantity_step` of `rules`, then checks
+    /// the order type and the notional limits from the same rules.
+    /// Market orders are checked against the market limits, skipping notional checks without a reference price.
+    pub fn normalize_order(&self, order_type: OrderType, price: Number, quantity: Number) -> std::result::Result<NormalizedOrder, OrderValidationError> {
+        self.check_order_type(order_type)?;
+
+        let rules = self.rules();
+        let price = round_to_step(price, rules.price_tick);
+        let quantity = floor_to_step(quantity, rules.quantity_step);
+
+        if quantity <= ZERO {
+            return Err(OrderValidationError::InvalidQuantity { quantity, step: rules.quantity_step });
+        }
+
+        let is_market = order_type == OrderType::MARKET;
+
+        if price <= ZERO {
+            if is_market {
+                return Ok(NormalizedOrder { price, quantity });
+            }
+            return Err(OrderValidationError::InvalidPrice { price, tick: rules.price_tick });
+        }
+
+        check_notional(&rules, is_market, price * quantity)?;
+
+        Ok(NormalizedOrder { price, quantity })
+    }
+
+    /// Floors the `quoteOrderQty` of a market order to `quote_asset_precision` and checks it against
+    /// the market notional limits.
+    pub fn normalize_quote_order(&self, order_type: OrderType, quote_order_qty: Number) -> std::result::Result<Number, OrderValidationError> {
+        self.check_order_type(order_type)?;
+
+        let quote_order_qty = floor_to(quote_order_qty, self.quote_asset_precision);
+
+        if quote_order_qty <= ZERO {
+            return Err(OrderValidationError::InvalidQuantity { quantity: quote_order_qty, step: step_size(self.quote_asset_precision) });
+        }
+
+        check_notional(&self.rules(), true, quote_order_qty)?;
+
+        Ok(quote_order_qty)
+    }
+
+    fn check_order_type(&self, order_type: OrderType) -> std::result::Result<(), OrderValidationError> {
+        if !self.is_trading() {
+            return Err(OrderValidationError::SymbolNotTrading { symbol: self.symbol.clone() });
+        }
+
+        if !self.supports(order_type) {
+            return Err(OrderValidationError::UnsupportedOrderType { symbol: self.symbol.clone(), order_type });
+        }
+        Ok(())
+    }
+}
+
+fn check_notional(rules: &SymbolRules, is_market: bool, notional: Number) -> std::result::Result<(), OrderValidationError> {
+    let (min, max) = if is_market {
+        (rules.min_notional_market, rules.max_notional_market)
+    } else {
+        (rules.min_notional, rules.max_notional)
+    };
+
+    if notional < min {
+        return Err(OrderValidationError::BelowMinNotional { notional, min });
+    }
+
+    if max > ZERO && notional > max {
+        return Err(OrderValidationError::AboveMaxNotional { notional, max });
+    }
+    Ok(())
+}
+
+impl Order {
+
+    /// Returns the order with price and quantity rounded to the symbol rules, see `SymbolInfo::normalize_order`.
+    pub fn normalized(mut self, info: &SymbolInfo) -> std::result::Result<Self, OrderValidationError> {
+        if self.symbol != info.symbol {
+            return Err(OrderValidationError::SymbolMismatch { symbol: info.symbol.clone(), order_symbol: self.symbol });
+        }
+
+        let normalized = info.normalize_order(self.order_type, self.price, self.quantity)?;
+        self.price = normalized.price;
+        self.quantity = normalized.quantity;
+        Ok(self)
+    }
+}
+
+impl OrderRequest {
+
+    /// Returns the request with price and quantity rounded to the symbol rules where it has them, see
+    /// `SymbolInfo::normalize_order`. A `market_quote` order gets its `quote_order_qty` checked by
+    /// `SymbolInfo::normalize_quote_order` instead.
+    pub fn normalized(mut self, info: &SymbolInfo) -> std::result::Result<Self, OrderValidationError> {
+        if self.symbol != info.symbol {
+            return Err(OrderValidationError::SymbolMismatch { symbol: info.symbol.clone(), order_symbol: self.symbol });
+        }
+
+        if let Some(quote_order_qty) = self.quote_order_qty {
+            self.quote_order_qty = Some(info.normalize_quote_order(self.order_type, quote_order_qty)?);
+            return Ok(self);
+        }
+
+        let normalized = info.normalize_order(self.order_type, self.price.unwrap_or(ZERO), self.quantity.unwrap_or(ZERO))?;
+        self.price = self.price.map(|_| normalized.price);
+        self.quantity = Some(normalized.quantity);
+        Ok(self)
+    }
+}
+
+impl Mexc {
+
+    /// Normalizes the order against `info` and only submits it when it passes validation.
+    pub async fn submit_normalized_order(&self, info: &SymbolInfo, order: OrderRequest, recv_window: Option<u64>) -> Result<OrderReceipt> {
+        let order = order.normalized(info)?;
+        self.submit_order(order, recv_window).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::orders::OrderSide;
+    use crate::utils::number;
+
+    use crate::market::tests::PLS_SYMBOL;
+
+    /// PLSUSDT limited to LIMIT and MARKET orders
+    fn symbol_json() -> String {
+        PLS_SYMBOL.replace(r#"["LIMIT","MARKET","LIMIT_MAKER","STOP_LIMIT"]"#, r#"["LIMIT","MARKET"]"#)
+    }
+
+    #[test]
+    fn test_normalize_order() {
+        let info: SymbolInfo = serde_json::from_str(&symbol_json()).unwrap();
+
+        let order = Order {
+            symbol: "PLSUSDT".into(),
+            price: number(0.000095123456),
+            quantity: number(599971.139),
+            side: OrderSide::SELL,
+            order_type: OrderType::LIMIT,
+            client_order_id: None
+        }.normalized(&info).unwrap();
+
+        assert_eq!(order.price, number(0.00009512));
+        assert_eq!(order.quantity, number(599971.13));
+
+        let err = info.normalize_order(OrderType::LIMIT_MAKER, number(0.0001), number(100000.0)).unwrap_err();
+        assert!(matches!(err, OrderValidationError::UnsupportedOrderType { .. }));
+
+        let err = info.normalize_order(OrderType::LIMIT, number(0.0001), number(100.0)).unwrap_err();
+        assert!(matches!(err, OrderValidationError::BelowMinNotional { .. }));
+
+        let err = info.normalize_order(OrderType::LIMIT, number(0.0001), number(0.001)).unwrap_err();
+        assert!(matches!(err, OrderValidationError::InvalidQuantity { .. }));
+
+        // without a reference price a market order only gets its quantity floored
+        let market = info.normalize_order(OrderType::MARKET, ZERO, number(1000.005)).unwrap();
+        assert_eq!(market, NormalizedOrder { price: ZERO, quantity: number(1000.0) });
+
+        let err = info.normalize_order(OrderType::LIMIT, ZERO, number(1000.0)).unwrap_err();
+        assert!(matches!(err, OrderValidationError::InvalidPrice { .. }));
+
+        // the quantity step follows baseSizePrecision when the exchange sets it
+        let lots: SymbolInfo = serde_json::from_str(&symbol_json().replace(r#""baseSizePrecision":"0""#, r#""baseSizePrecision":"10""#)).unwrap();
+        let order = lots.normalize_order(OrderType::LIMIT, number(0.000095123456), number(599975.5)).unwrap();
+        assert_eq!(order.quantity, number(599970.0));
+    }
+
+    #[test]
+    fn test_normalize_order_request() {
+        let info: SymbolInfo = serde_json::from_str(&symbol_json()).unwrap();
+
+        let limit = OrderRequest::limit("PLSUSDT", OrderSide::BUY, number(0.000095123456), number(599971.139))
+            .normalized(&info).unwrap();
+        assert_eq!(limit.price(), Some(number(0.00009512)));
+        assert_eq!(limit.quantity(), Some(number(599971.13)));
+
+        let market = OrderRequest::market("PLSUSDT", OrderSide::SELL, number(1000.005)).normalized(&info).unwrap();
+        assert_eq!(market.price(), None);
+        assert_eq!(market.quantity(), Some(number(1000.0)));
+
+        let quote = OrderRequest::market_quote("PLSUSDT", OrderSide::BUY, number(150.123456789)).normalized(&info).unwrap();
+        assert_eq!(quote.quote_order_qty(), Some(number(150.12345678)));
+        assert_eq!(quote.quantity(), None);
+
+        let err = OrderRequest::market_quote("PLSUSDT", OrderSide::BUY, number(0.5)).normalized(&info).unwrap_err();
+        assert!(matches!(err, OrderValidationError::BelowMinNotional { .. }));
+
+        let err = OrderRequest::market_quote("PLSUSDT", OrderSide::BUY, number(200000.0)).normalized(&info).unwrap_err();
+        assert!(matches!(err, OrderValidationError::AboveMaxNotional { .. }));
+
+        let err = OrderRequest::market("BTCUSDT", OrderSide::BUY, number(1.0)).normalized(&info).unwrap_err();
+        assert!(matches!(err, OrderValidationError::SymbolMismatch { .. }));
+    }
+}
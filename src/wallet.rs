@@ -0,0 +1,598 @@
+use crate::{Mexc, MexcError, error::Result, request::{Params, collect_windows}};
+use crate::utils::{Number, format_number, parse_string_to_number};
+use reqwest::Method;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use thiserror::Error;
+
+/// Widest `startTime`..`endTime` span used per deposit or withdraw history request.
+pub const WALLET_HISTORY_WINDOW_MS: u128 = 7 * 24 * 3600 * 1000;
+
+/// Max records per deposit or withdraw history request.
+pub const WALLET_HISTORY_LIMIT: u32 = 1000;
+
+/// Max records per page of `/api/v3/capital/transfer` history.
+pub const TRANSFER_PAGE_SIZE: u32 = 100;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CoinConfig {
+    pub coin: String,
+    pub name: String,
+    #[serde(rename = "networkList")]
+    pub networks: Vec<NetworkConfig>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct NetworkConfig {
+    pub coin: String,
+    pub name: String,
+    /// Network id to pass as `netWork` in wallet requests
+    #[serde(rename = "netWork")]
+    pub network: String,
+    #[serde(rename = "depositEnable")]
+    pub deposit_enable: bool,
+    #[serde(rename = "withdrawEnable")]
+    pub withdraw_enable: bool,
+    #[serde(rename = "minConfirm", default)]
+    pub min_confirm: u32,
+    #[serde(rename = "withdrawFee", deserialize_with = "parse_string_to_number")]
+    pub withdraw_fee: Number,
+    #[serde(rename = "withdrawMin", deserialize_with = "parse_string_to_number")]
+    pub withdraw_min: Number,
+    #[serde(rename = "withdrawMax", deserialize_with = "parse_string_to_number")]
+    pub withdraw_max: Number,
+    #[serde(rename = "sameAddress", default)]
+    pub same_address: bool,
+    pub contract: Option<String>,
+    #[serde(rename = "depositTips")]
+    pub deposit_tips: Option<String>,
+    #[serde(rename = "withdrawTips")]
+    pub withdraw_tips: Option<String>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DepositAddress {
+    pub coin: String,
+    pub network: String,
+    pub address: String,
+    pub memo: Option<String>
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "u32")]
+pub enum DepositStatus {
+    Small,
+    TimeDelay,
+    LargeDelay,
+    Pending,
+    Success,
+    Auditing,
+    Rejected,
+    Refund,
+    PreSuccess,
+    Invalid,
+    Restricted,
+    Completed,
+    Unknown(u32)
+}
+
+impl From<u32> for DepositStatus {
+    fn from(status: u32) -> Self {
+        match status {
+            1 => DepositStatus::Small,
+            2 => DepositStatus::TimeDelay,
+            3 => DepositStatus::LargeDelay,
+            4 => DepositStatus::Pending,
+            5 => DepositStatus::Success,
+            6 => DepositStatus::Auditing,
+            7 => DepositStatus::Rejected,
+            8 => DepositStatus::Refund,
+            9 => DepositStatus::PreSuccess,
+            10 => DepositStatus::Invalid,
+            11 => DepositStatus::Restricted,
+            12 => DepositStatus::Completed,
+            other => DepositStatus::Unknown(other)
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Deposit {
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub amount: Number,
+    pub coin: String,
+    pub network: String,
+    pub status: DepositStatus,
+    pub address: String,
+    pub memo: Option<String>,
+    #[serde(rename = "txId")]
+    pub tx_id: Option<String>,
+    #[serde(rename = "insertTime")]
+    pub insert_time: u128,
+    #[serde(rename = "unlockConfirm")]
+    pub unlock_confirm: Option<String>,
+    #[serde(rename = "confirmTimes")]
+    pub confirm_times: Option<String>
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "u32")]
+pub enum WithdrawStatus {
+    Apply,
+    Auditing,
+    Wait,
+    Processing,
+    WaitPackaging,
+    WaitConfirm,
+    Success,
+    Failed,
+    Cancel,
+    Manual,
+    Unknown(u32)
+}
+
+impl From<u32> for WithdrawStatus {
+    fn from(status: u32) -> Self {
+        match status {
+            1 => WithdrawStatus::Apply,
+            2 => WithdrawStatus::Auditing,
+            3 => WithdrawStatus::Wait,
+            4 => WithdrawStatus::Processing,
+            5 => WithdrawStatus::WaitPackaging,
+            6 => WithdrawStatus::WaitConfirm,
+            7 => WithdrawStatus::Success,
+            8 => WithdrawStatus::Failed,
+            9 => WithdrawStatus::Cancel,
+            10 => WithdrawStatus::Manual,
+            other => WithdrawStatus::Unknown(other)
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Withdrawal {
+    pub id: String,
+    #[serde(rename = "txId")]
+    pub tx_id: Option<String>,
+    pub coin: String,
+    pub network: String,
+    pub address: String,
+    pub memo: Option<String>,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub amount: Number,
+    #[serde(rename = "transactionFee", deserialize_with = "parse_string_to_number")]
+    pub transaction_fee: Number,
+    pub status: WithdrawStatus,
+    /// 0 for an on-chain withdrawal, 1 for an internal transfer
+    #[serde(rename = "transferType")]
+    pub transfer_type: Option<u32>,
+    #[serde(rename = "applyTime")]
+    pub apply_time: u128,
+    #[serde(rename = "updateTime")]
+    pub update_time: Option<u128>,
+    pub remark: Option<String>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WithdrawId {
+    pub id: String
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawRequest {
+    pub coin: String,
+    /// Network id, `NetworkConfig::network`
+    pub network: String,
+    pub address: String,
+    pub amount: Number,
+    pub memo: Option<String>,
+    /// Our own id, sent as `withdrawOrderId`
+    pub withdraw_order_id: Option<String>,
+    pub remark: Option<String>
+}
+
+impl WithdrawRequest {
+
+    pub fn new(coin: &str, network: &str, address: &str, amount: Number) -> Self {
+        WithdrawRequest {
+            coin: coin.to_string(),
+            network: network.to_string(),
+            address: address.to_string(),
+            amount,
+            memo: None,
+            withdraw_order_id: None,
+            remark: None
+        }
+    }
+
+    pub fn with_memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    pub fn with_withdraw_order_id(mut self, withdraw_order_id: impl Into<String>) -> Self {
+        self.withdraw_order_id = Some(withdraw_order_id.into());
+        self
+    }
+
+    pub fn with_remark(mut self, remark: impl Into<String>) -> Self {
+        self.remark = Some(remark.into());
+        self
+    }
+
+    fn params(&self) -> Params {
+        Params::new()
+            .add("coin", &self.coin)
+            .add("netWork", &self.network)
+            .add("address", &self.address)
+            .add("amount", format_number(self.amount))
+            .add_opt("memo", self.memo.as_deref())
+            .add_opt("withdrawOrderId", self.withdraw_order_id.as_deref())
+            .add_opt("remark", self.remark.as_deref())
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum WithdrawPolicyError {
+    #[error("{address} with memo {memo:?} on {coin}/{network} is not an allowed destination")]
+    AddressNotAllowed { coin: String, network: String, address: String, memo: Option<String> },
+
+    #[error("{amount} {coin} is above the maximum of {max}")]
+    AboveMaxAmount { coin: String, amount: Number, max: Number }
+}
+
+/// Client side limits on `Mexc::withdraw`, set with `MexcBuilder::withdraw_policy`.
+/// Only allowlisted (coin, network, address, memo) destinations pass, an empty policy rejects every withdrawal.
+/// Coins are matched case insensitively, networks, addresses and memos exactly. On memo routed networks
+/// (EOS, XRP, TON, exchange deposit addresses) the memo picks the recipient, so it is part of the destination.
+#[derive(Debug, Clone, Default)]
+pub struct WithdrawPolicy {
+    destinations: HashSet<(String, String, String, Option<String>)>,
+    max_amounts: HashMap<String, Number>
+}
+
+impl WithdrawPolicy {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows `address` for withdrawals without a memo.
+    pub fn allow(mut self, coin: &str, network: &str, address: &str) -> Self {
+        self.destinations.insert((coin.to_uppercase(), network.to_string(), address.to_string(), None));
+        self
+    }
+
+    /// Allows `address` only together with `memo`.
+    pub fn allow_with_memo(mut self, coin: &str, network: &str, address: &str, memo: &str) -> Self {
+        self.destinations.insert((coin.to_uppercase(), network.to_string(), address.to_string(), Some(memo.to_string())));
+        self
+    }
+
+    /// Caps a single withdrawal of `coin`, coins without a cap are only limited by the allowlist.
+    pub fn max_amount(mut self, coin: &str, max: Number) -> Self {
+        self.max_amounts.insert(coin.to_uppercase(), max);
+        self
+    }
+
+    pub fn check(&self, request: &WithdrawRequest) -> std::result::Result<(), WithdrawPolicyError> {
+        let coin = request.coin.to_uppercase();
+
+        let destination = (coin.clone(), request.network.clone(), request.address.clone(), request.memo.clone());
+
+        if !self.destinations.contains(&destination) {
+            return Err(WithdrawPolicyError::AddressNotAllowed {
+                coin: request.coin.clone(),
+                network: request.network.clone(),
+                address: request.address.clone(),
+                memo: request.memo.clone()
+            });
+        }
+
+        if let Some(max) = self.max_amounts.get(&coin) {
+            if request.amount > *max {
+                return Err(WithdrawPolicyError::AboveMaxAmount { coin: request.coin.clone(), amount: request.amount, max: *max });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccountType {
+    SPOT,
+    FUTURES,
+    ISOLATED_MARGIN,
+    /// Account types this crate doesn't know yet, only seen in responses
+    #[serde(other)]
+    OTHER
+}
+
+impl fmt::Display for AccountType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountType::SPOT => write!(f, "SPOT"),
+            AccountType::FUTURES => write!(f, "FUTURES"),
+            AccountType::ISOLATED_MARGIN => write!(f, "ISOLATED_MARGIN"),
+            AccountType::OTHER => write!(f, "OTHER"),
+        }
+    }
+}
+
+impl AccountType {
+
+    /// Value sent as `fromAccountType` / `toAccountType`. `OTHER` only comes back in responses
+    /// and is rejected before the request is signed.
+    pub fn request_value(self) -> Result<&'static str> {
+        match self {
+            AccountType::SPOT => Ok("SPOT"),
+            AccountType::FUTURES => Ok("FUTURES"),
+            AccountType::ISOLATED_MARGIN => Ok("ISOLATED_MARGIN"),
+            AccountType::OTHER => Err(MexcError::InvalidRequest("account type OTHER can't be sent to the exchange".into()))
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TransferId {
+    #[serde(rename = "tranId")]
+    pub tran_id: String
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Transfer {
+    #[serde(rename = "tranId")]
+    pub tran_id: String,
+    #[serde(rename = "clientTranId")]
+    pub client_tran_id: Option<String>,
+    pub asset: String,
+    #[serde(deserialize_with = "parse_string_to_number")]
+    pub amount: Number,
+    #[serde(rename = "fromAccountType")]
+    pub from_account_type: AccountType,
+    #[serde(rename = "toAccountType")]
+    pub to_account_type: AccountType,
+    #[serde(rename = "fromSymbol")]
+    pub from_symbol: Option<String>,
+    #[serde(rename = "toSymbol")]
+    pub to_symbol: Option<String>,
+    /// SUCCESS, FAILED or WAIT
+    pub status: String,
+    pub timestamp: u128
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TransferPage {
+    pub rows: Vec<Transfer>,
+    pub total: u64
+}
+
+impl Mexc {
+
+    /// Moves `amount` of `asset` between our own accounts, returns the transfer id.
+    pub async fn transfer(&self, from: AccountType, to: AccountType, asset: &str, amount: Number) -> Result<String> {
+        let params = Params::new()
+            .add("fromAccountType", from.request_value()?)
+            .add("toAccountType", to.request_value()?)
+            .add("asset", asset)
+            .add("amount", format_number(amount));
+
+        let resp: TransferId = self.signed_request(Method::POST, "/api/v3/capital/transfer", params).await?;
+        Ok(resp.tran_id)
+    }
+
+    /// Moves `amount` of `asset` from spot to the futures account, where it shows up in `FuturesBalance::available_balance`.
+    pub async fn top_up_futures_margin(&self, asset: &str, amount: Number) -> Result<String> {
+        self.transfer(AccountType::SPOT, AccountType::FUTURES, asset, amount).await
+    }
+
+    /// One page of transfers from `from` to `to`, MEXC defaults to the last 7 days. page starts at 1, size: default 10; max 100
+    pub async fn get_transfer_history(&self, from: AccountType, to: AccountType, start: Option<u128>, end: Option<u128>, page: Option<u32>, size: Option<u32>) -> Result<TransferPage> {
+        let params = Params::new()
+            .add("fromAccountType", from.request_value()?)
+            .add("toAccountType", to.request_value()?)
+            .add_opt("startTime", start)
+            .add_opt("endTime", end)
+            .add_opt("page", page)
+            .add_opt("size", size);
+
+        self.signed_request(Method::GET, "/api/v3/capital/transfer", params).await
+    }
+
+    /// Every transfer from `from` to `to` between `start` and `end`, walking all pages.
+    pub async fn get_transfer_history_all(&self, from: AccountType, to: AccountType, start: Option<u128>, end: Option<u128>) -> Result<Vec<Transfer>> {
+        let mut transfers = vec![];
+
+        for page in 1.. {
+            let resp = self.get_transfer_history(from, to, start, end, Some(page), Some(TRANSFER_PAGE_SIZE)).await?;
+            let page_len = resp.rows.len();
+            transfers.extend(resp.rows);
+
+            if page_len < TRANSFER_PAGE_SIZE as usize || transfers.len() as u64 >= resp.total {
+                break;
+            }
+        }
+
+        Ok(transfers)
+    }
+
+    pub async fn get_transfer(&self, tran_id: &str) -> Result<Transfer> {
+        let params = Params::new().add("tranId", tran_id);
+        self.signed_request(Method::GET, "/api/v3/capital/transfer/tranId", params).await
+    }
+
+    /// Submits a withdrawal after checking it against `withdraw_policy`, returns the withdrawal id.
+    pub async fn withdraw(&self, request: &WithdrawRequest) -> Result<String> {
+        if let Some(policy) = &self.withdraw_policy {
+            policy.check(request)?;
+        }
+
+        let resp: WithdrawId = self.signed_request(Method::POST, "/api/v3/capital/withdraw", request.params()).await?;
+        Ok(resp.id)
+    }
+
+    /// Cancels a withdrawal that is still awaiting processing.
+    pub async fn cancel_withdraw(&self, id: &str) -> Result<String> {
+        let params = Params::new().add("id", id);
+
+        let resp: WithdrawId = self.signed_request(Method::DELETE, "/api/v3/capital/withdraw", params).await?;
+        Ok(resp.id)
+    }
+
+    /// Deposit and withdraw settings of every coin on every network.
+    pub async fn get_coin_configs(&self) -> Result<Vec<CoinConfig>> {
+        self.signed_request(Method::GET, "/api/v3/capital/config/getall", Params::new()).await
+    }
+
+    /// Existing deposit addresses for `coin`, on all networks when `network` is `None`.
+    pub async fn get_deposit_addresses(&self, coin: &str, network: Option<&str>) -> Result<Vec<DepositAddress>> {
+        let params = Params::new()
+            .add("coin", coin)
+            .add_opt("netWork", network);
+
+        self.signed_request(Method::GET, "/api/v3/capital/deposit/address", params).await
+    }
+
+    pub async fn generate_deposit_address(&self, coin: &str, network: &str) -> Result<DepositAddress> {
+        let params = Params::new()
+            .add("coin", coin)
+            .add("netWork", network);
+
+        self.signed_request(Method::POST, "/api/v3/capital/deposit/address", params).await
+    }
+
+    /// Deposits in ms timestamps `start`..=`end`, MEXC defaults to the last 7 days. limit: default 1000; max 1000
+    pub async fn get_deposit_history(&self, coin: Option<&str>, start: Option<u128>, end: Option<u128>, limit: Option<u32>) -> Result<Vec<Deposit>> {
+        let params = Params::new()
+            .add_opt("coin", coin)
+            .add_opt("startTime", start)
+            .add_opt("endTime", end)
+            .add_opt("limit", limit);
+
+        self.signed_request(Method::GET, "/api/v3/capital/deposit/hisrec", params).await
+    }
+
+    /// Every deposit between `start` and `end`, split into 7 day windows.
+    pub async fn get_deposit_history_range(&self, coin: Option<&str>, start: u128, end: u128) -> Result<Vec<Deposit>> {
+        let mut deposits = collect_windows(start, end, WALLET_HISTORY_WINDOW_MS, WALLET_HISTORY_LIMIT as usize, |from, to| {
+            self.get_deposit_history(coin, Some(from), Some(to), Some(WALLET_HISTORY_LIMIT))
+        }).await?;
+
+        deposits.sort_by_key(|d| d.insert_time);
+        Ok(deposits)
+    }
+
+    /// Withdrawals in ms timestamps `start`..=`end`, MEXC defaults to the last 7 days. limit: default 1000; max 1000
+    pub async fn get_withdraw_history(&self, coin: Option<&str>, start: Option<u128>, end: Option<u128>, limit: Option<u32>) -> Result<Vec<Withdrawal>> {
+        let params = Params::new()
+            .add_opt("coin", coin)
+            .add_opt("startTime", start)
+            .add_opt("endTime", end)
+            .add_opt("limit", limit);
+
+        self.signed_request(Method::GET, "/api/v3/capital/withdraw/history", params).await
+    }
+
+    /// Every withdrawal between `start` and `end`, split into 7 day windows.
+    pub async fn get_withdraw_history_range(&self, coin: Option<&str>, start: u128, end: u128) -> Result<Vec<Withdrawal>> {
+        let mut withdrawals = collect_windows(start, end, WALLET_HISTORY_WINDOW_MS, WALLET_HISTORY_LIMIT as usize, |from, to| {
+            self.get_withdraw_history(coin, Some(from), Some(to), Some(WALLET_HISTORY_LIMIT))
+        }).await?;
+
+        withdrawals.sort_by_key(|w| w.apply_time);
+        Ok(withdrawals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::utils::{number, unlock_keys, get_timestamp};
+
+    #[test]
+    fn test_decode_wallet() {
+        let json = r#"[{"coin":"EOS","name":"EOS","networkList":[{"coin":"EOS","depositDesc":null,"depositEnable":true,"minConfirm":0,"name":"EOS","network":"EOS","withdrawEnable":false,"withdrawFee":"0.000100000000000000","withdrawIntegerMultiple":null,"withdrawMax":"10000.000000000000000000","withdrawMin":"0.001000000000000000","sameAddress":false,"contract":"TN3W4H6rK2ce4vX9YnFQHwKENnHjoxb3m9","withdrawTips":null,"depositTips":null,"netWork":"EOS"}]}]"#;
+        let configs: Vec<CoinConfig> = serde_json::from_str(json).unwrap();
+        assert_eq!(configs[0].networks[0].withdraw_min, number(0.001));
+
+        let json = r#"[{"amount":"50000","coin":"EOS","network":"EOS","status":5,"address":"0x20b7cf77db93d6ef1ab979c49d3ac3c3cf4e6f8e","txId":"0x01a0b0c4ae8a8e7d1b2f9a9c2d1e2f3a","insertTime":1659513342000,"unlockConfirm":"10","confirmTimes":"241","memo":"xxyy1122"}]"#;
+        let deposits: Vec<Deposit> = serde_json::from_str(json).unwrap();
+        assert_eq!(deposits[0].status, DepositStatus::Success);
+
+        let json = r#"[{"id":"bb17a2d452684f00a523c015d512a341","txId":null,"coin":"EOS","network":"EOS","address":"zzqqqqqqqqqq","amount":"10","transferType":0,"status":13,"transactionFee":"0","confirmNo":null,"applyTime":1665300874000,"remark":"","memo":"MX10086","transHash":"0x0ced593b8b5adc9f600334d0d7335456a7ed772ea5547beda7ffc4f33a065c","updateTime":1712134082000,"coinId":"128f589271cb4951b03e71e6323eb7be","vcoinId":"af42c6414b9a46c8869ce30fd51660f3"}]"#;
+        let withdrawals: Vec<Withdrawal> = serde_json::from_str(json).unwrap();
+        assert_eq!(withdrawals[0].status, WithdrawStatus::Unknown(13));
+    }
+
+    #[test]
+    fn test_withdraw_policy() {
+        let policy = WithdrawPolicy::new()
+            .allow("usdt", "TRX", "TXb1XXX")
+            .max_amount("USDT", number(1000.0));
+
+        assert!(policy.check(&WithdrawRequest::new("USDT", "TRX", "TXb1XXX", number(500.0))).is_ok());
+
+        let err = policy.check(&WithdrawRequest::new("USDT", "TRX", "TAttacker", number(500.0))).unwrap_err();
+        assert!(matches!(err, WithdrawPolicyError::AddressNotAllowed { .. }));
+
+        let err = policy.check(&WithdrawRequest::new("USDT", "TRX", "TXb1XXX", number(1500.0))).unwrap_err();
+        assert!(matches!(err, WithdrawPolicyError::AboveMaxAmount { .. }));
+
+        let err = WithdrawPolicy::new().check(&WithdrawRequest::new("USDT", "TRX", "TXb1XXX", number(1.0))).unwrap_err();
+        assert!(matches!(err, WithdrawPolicyError::AddressNotAllowed { .. }));
+
+        // a memo routed destination only passes with its exact memo
+        let policy = WithdrawPolicy::new().allow_with_memo("XRP", "XRP", "rExchange", "12345");
+        let to_exchange = WithdrawRequest::new("XRP", "XRP", "rExchange", number(10.0));
+
+        assert!(policy.check(&to_exchange.clone().with_memo("12345")).is_ok());
+        assert!(policy.check(&to_exchange.clone().with_memo("99999")).is_err());
+        assert!(policy.check(&to_exchange.clone().with_memo("12345 ")).is_err());
+        assert!(policy.check(&to_exchange).is_err());
+
+        // and an address allowed without a memo rejects one
+        let err = WithdrawPolicy::new().allow("USDT", "TRX", "TXb1XXX")
+            .check(&WithdrawRequest::new("USDT", "TRX", "TXb1XXX", number(1.0)).with_memo("1")).unwrap_err();
+        assert!(matches!(err, WithdrawPolicyError::AddressNotAllowed { memo: Some(_), .. }));
+    }
+
+    #[test]
+    fn test_decode_transfers() {
+        let json = r#"{"rows":[{"tranId":"11945860693","clientTranId":null,"asset":"USDT","amount":"100","fromAccountType":"SPOT","toAccountType":"FUTURES","fromSymbol":null,"toSymbol":null,"status":"SUCCESS","timestamp":1678603205000},{"tranId":"11945860694","clientTranId":null,"asset":"USDT","amount":"5","fromAccountType":"SPOT","toAccountType":"C2C","fromSymbol":null,"toSymbol":null,"status":"WAIT","timestamp":1678603206000}],"total":2}"#;
+        let page: TransferPage = serde_json::from_str(json).unwrap();
+
+        assert_eq!(page.rows[0].to_account_type, AccountType::FUTURES);
+        assert_eq!(page.rows[1].to_account_type, AccountType::OTHER);
+        assert_eq!(AccountType::ISOLATED_MARGIN.request_value().unwrap(), "ISOLATED_MARGIN");
+        assert!(AccountType::OTHER.request_value().is_err());
+        assert_eq!(page.rows[0].amount, number(100.0));
+    }
+
+    #[tokio::test]
+    async fn test_wallet_history() {
+        let (key, secret) = unlock_keys().unwrap();
+        let client = Mexc::new(Some(key),Some(secret),None).unwrap();
+
+        let configs = client.get_coin_configs().await.unwrap();
+        dbg!(configs.iter().find(|c| c.coin == "USDT"));
+
+        let addresses = client.get_deposit_addresses("USDT", None).await.unwrap();
+        dbg!(addresses);
+
+        let end = get_timestamp();
+        let start = end - 30 * 24 * 3600 * 1000;
+
+        let deposits = client.get_deposit_history_range(None, start, end).await.unwrap();
+        dbg!(deposits);
+
+        let withdrawals = client.get_withdraw_history_range(None, start, end).await.unwrap();
+        dbg!(withdrawals);
+
+        let transfers = client.get_transfer_history_all(AccountType::SPOT, AccountType::FUTURES, Some(start), Some(end)).await.unwrap();
+        dbg!(transfers);
+    }
+}